@@ -1,13 +1,15 @@
+use elliptic_curve::curve::CurveParams;
 use elliptic_curve::ecdsa::*;
-use num_bigint::BigUint;
-use rand::Rng;
+use elliptic_curve::secp256k1::Secp256k1Params;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
 
 fn main() {
     let mut rng = rand::thread_rng();
-    let d: BigUint = rng.sample(num_bigint::RandomBits::new(256));
+    let d = rng.gen_biguint_range(&BigUint::one(), &Secp256k1Params::order());
 
-    // Create a new ECDSA instance with the private key d
-    let ecdsa = Ecdsa::new(d);
+    // Create a new ECDSA instance, new returns None unless the private key d is in [1, n - 1]
+    let ecdsa: Ecdsa = Ecdsa::new(d).unwrap();
 
     let message = "No.more.caffeine";
 
@@ -16,11 +18,11 @@ fn main() {
     println!("Hash: {}", hash_value);
 
     // Sign the message
-    let (r, s) = ecdsa.sign(message);
-    println!("Signature: (r: {}, s: {})", r, s);
+    let sig = ecdsa.sign(message);
+    println!("Signature: (r: {}, s: {})", sig.r(), sig.s());
 
     // Verify the signature
-    let is_valid = ecdsa.verify(message, &r, &s);
+    let is_valid = ecdsa.verify(message, &sig);
     if is_valid {
         println!("Signature is valid.");
     } else {
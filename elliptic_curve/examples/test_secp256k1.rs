@@ -35,6 +35,6 @@ fn main() {
         16,
     )
     .unwrap();
-    let point = secp256k1.lift_x(&x);
+    let point = secp256k1.lift_x(&x, false).expect("x is not on the curve");
     println!("{:?}", point);
 }
@@ -0,0 +1,107 @@
+use crate::curve::{Curve, CurveParams};
+use crate::pairing::{G2Point, PairingFamily, PairingParams, TwistType};
+
+/// Parameters of the BLS12-381 curve which is used in zcash and ethereum consensus
+/// https://hackmd.io/@benjaminion/bls12-381
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bls12_381Params;
+
+impl CurveParams for Bls12_381Params {
+    const NAME: &'static str = "bls12-381";
+    const P: &'static str = "1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+    const A: &'static str = "0";
+    const B: &'static str = "4";
+    const GX: &'static str = "17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const GY: &'static str = "08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1";
+    const N: &'static str = "73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
+    const H: &'static str = "396c8c005555e1568c00aaab0000aaab";
+}
+
+impl PairingParams for Bls12_381Params {
+    const FAMILY: PairingFamily = PairingFamily::Bls12;
+    const X: &'static str = "d201000000010000";
+    const X_IS_NEGATIVE: bool = true;
+    const XI: [&'static str; 2] = ["1", "1"];
+    const TWIST: TwistType = TwistType::M;
+    const G2X: [&'static str; 2] = [
+        "024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8",
+        "13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e",
+    ];
+    const G2Y: [&'static str; 2] = [
+        "0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801",
+        "0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be",
+    ];
+}
+
+/// BLS12-381 curve, the G1 group
+pub type Bls12_381 = Curve<Bls12_381Params>;
+
+/// Point in the G2 group of BLS12-381
+pub type Bls12_381G2 = G2Point<Bls12_381Params>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pairing::pairing;
+    use crate::point::Point;
+    use ark_ec::pairing::Pairing;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::PrimeField;
+    use finite_field::fp12::Fp12;
+    use finite_field::fp2::Fp2;
+    use finite_field::fp6::Fp6;
+    use finite_field::prime_field::PrimeField as FF;
+    use num_bigint::BigUint;
+
+    fn fq(x: ark_bls12_381::Fq) -> FF {
+        Bls12_381Params::field(BigUint::from(x.into_bigint()))
+    }
+
+    fn fq2(x: ark_bls12_381::Fq2) -> Fp2 {
+        Fp2::new(fq(x.c0), fq(x.c1))
+    }
+
+    fn fq12(x: ark_bls12_381::Fq12) -> Fp12 {
+        let fq6 = |y: ark_bls12_381::Fq6| {
+            Fp6::new(fq2(y.c0), fq2(y.c1), fq2(y.c2), Bls12_381Params::xi())
+        };
+        Fp12::new(fq6(x.c0), fq6(x.c1))
+    }
+
+    #[test]
+    fn test_bls12_381_g1() {
+        let g1 = Bls12_381::new();
+        assert!(g1.g().scalar_mul(g1.n().clone()).is_infinity());
+    }
+
+    #[test]
+    fn test_bls12_381_g2() {
+        let q = Bls12_381G2::generator();
+        let r = Bls12_381Params::order();
+        assert!(q.scalar_mul(&r).is_infinity());
+        // pi(Q) = [p]Q on the r-torsion
+        let p = Bls12_381Params::p() % &r;
+        assert_eq!(q.frobenius(), q.scalar_mul(&p));
+    }
+
+    /// cross-check the groups and the pairing against ark-bls12-381
+    #[test]
+    fn test_against_arkworks() {
+        let k = 0x1234567_u64;
+        let g1 = Bls12_381::new().g().scalar_mul(BigUint::from(k));
+        let g2 = Bls12_381G2::generator().scalar_mul(&BigUint::from(k));
+
+        let ark_g1 =
+            (ark_bls12_381::G1Affine::generator() * ark_bls12_381::Fr::from(k)).into_affine();
+        let ark_g2 =
+            (ark_bls12_381::G2Affine::generator() * ark_bls12_381::Fr::from(k)).into_affine();
+        assert_eq!(g1.xy(), (fq(ark_g1.x), fq(ark_g1.y)));
+        assert_eq!(g2.xy(), (fq2(ark_g2.x), fq2(ark_g2.y)));
+
+        // the final exponentiation of arkworks (https://eprint.iacr.org/2020/875) raises to
+        // 3(p^4 - p^2 + 1)/r instead of (p^4 - p^2 + 1)/r
+        let e = pairing(&g1, &Bls12_381G2::generator());
+        let ark_e = ark_bls12_381::Bls12_381::pairing(ark_g1, ark_bls12_381::G2Affine::generator());
+        assert_eq!(e.pow(&BigUint::from(3_u32)), fq12(ark_e.0));
+    }
+}
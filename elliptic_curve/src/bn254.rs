@@ -0,0 +1,100 @@
+use crate::curve::{Curve, CurveParams};
+use crate::pairing::{G2Point, PairingFamily, PairingParams, TwistType};
+
+/// Parameters of the BN254 curve (alt_bn128) which is used in ethereum precompiles
+/// https://eips.ethereum.org/EIPS/eip-197
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Bn254Params;
+
+impl CurveParams for Bn254Params {
+    const NAME: &'static str = "bn254";
+    const P: &'static str = "30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+    const A: &'static str = "0";
+    const B: &'static str = "3";
+    const GX: &'static str = "1";
+    const GY: &'static str = "2";
+    const N: &'static str = "30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+    const H: &'static str = "1";
+}
+
+impl PairingParams for Bn254Params {
+    const FAMILY: PairingFamily = PairingFamily::Bn;
+    const X: &'static str = "44e992b44a6909f1";
+    const X_IS_NEGATIVE: bool = false;
+    const XI: [&'static str; 2] = ["9", "1"];
+    const TWIST: TwistType = TwistType::D;
+    const G2X: [&'static str; 2] = [
+        "1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+        "198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2",
+    ];
+    const G2Y: [&'static str; 2] = [
+        "12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+        "090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b",
+    ];
+}
+
+/// BN254 curve, the G1 group
+pub type Bn254 = Curve<Bn254Params>;
+
+/// Point in the G2 group of BN254
+pub type Bn254G2 = G2Point<Bn254Params>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::from_hex;
+    use crate::pairing::pairing;
+    use crate::point::Point;
+    use ark_ec::pairing::Pairing;
+    use ark_ec::{AffineRepr, CurveGroup};
+    use ark_ff::PrimeField;
+    use finite_field::fp12::Fp12;
+    use finite_field::fp2::Fp2;
+    use finite_field::fp6::Fp6;
+    use finite_field::prime_field::PrimeField as FF;
+    use num_bigint::BigUint;
+
+    fn fq(x: ark_bn254::Fq) -> FF {
+        Bn254Params::field(BigUint::from(x.into_bigint()))
+    }
+
+    fn fq2(x: ark_bn254::Fq2) -> Fp2 {
+        Fp2::new(fq(x.c0), fq(x.c1))
+    }
+
+    fn fq12(x: ark_bn254::Fq12) -> Fp12 {
+        let fq6 = |y: ark_bn254::Fq6| Fp6::new(fq2(y.c0), fq2(y.c1), fq2(y.c2), Bn254Params::xi());
+        Fp12::new(fq6(x.c0), fq6(x.c1))
+    }
+
+    #[test]
+    fn test_bn254_g2() {
+        let q = Bn254G2::generator();
+        let r = Bn254Params::order();
+        assert!(q.scalar_mul(&r).is_infinity());
+        // pi(Q) = [p]Q on the r-torsion
+        let p = Bn254Params::p() % &r;
+        assert_eq!(q.frobenius(), q.scalar_mul(&p));
+    }
+
+    /// cross-check the groups and the pairing against ark-bn254
+    #[test]
+    fn test_against_arkworks() {
+        let k = 0x1234567_u64;
+        let g1 = Bn254::new().g().scalar_mul(BigUint::from(k));
+        let g2 = Bn254G2::generator().scalar_mul(&BigUint::from(k));
+
+        let ark_g1 = (ark_bn254::G1Affine::generator() * ark_bn254::Fr::from(k)).into_affine();
+        let ark_g2 = (ark_bn254::G2Affine::generator() * ark_bn254::Fr::from(k)).into_affine();
+        assert_eq!(g1.xy(), (fq(ark_g1.x), fq(ark_g1.y)));
+        assert_eq!(g2.xy(), (fq2(ark_g2.x), fq2(ark_g2.y)));
+
+        // the final exponentiation of arkworks (Fuentes-Castaneda et al.) raises to
+        // 2x(6x^2 + 3x + 1)(p^4 - p^2 + 1)/r instead of (p^4 - p^2 + 1)/r
+        let x = from_hex(Bn254Params::X);
+        let lambda = &x * 2_u32 * (&x * &x * 6_u32 + &x * 3_u32 + 1_u32);
+        let e = pairing(&g1, &Bn254G2::generator());
+        let ark_e = ark_bn254::Bn254::pairing(ark_g1, ark_bn254::G2Affine::generator());
+        assert_eq!(e.pow(&lambda), fq12(ark_e.0));
+    }
+}
@@ -0,0 +1,785 @@
+use crate::glv::Glv;
+use crate::msm::msm;
+use crate::point::{ECCPoint, Point, PointData};
+use crate::projective::constant_time_mul;
+use crate::scalar::Scalar;
+use crate::wnaf::{shamir_mul, wnaf_mul, FixedBaseTable, WNAF_WINDOW};
+use finite_field::ff::FiniteField;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::BigUint;
+use num_traits::Num;
+use std::fmt::Debug;
+use std::iter::Sum;
+use std::marker::PhantomData;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::sync::OnceLock;
+
+/// Parameters of a short weierstrass curve y^2 = x^3 + ax + b over F_p
+///
+/// all the numbers are given as hex strings
+pub trait CurveParams: Debug + Clone + PartialEq + 'static {
+    /// name of the curve
+    const NAME: &'static str;
+
+    /// prime of the base field
+    const P: &'static str;
+
+    /// coefficient a
+    const A: &'static str;
+
+    /// coefficient b
+    const B: &'static str;
+
+    /// x coordinate of the generator point
+    const GX: &'static str;
+
+    /// y coordinate of the generator point
+    const GY: &'static str;
+
+    /// order of the generator point
+    const N: &'static str;
+
+    /// cofactor h = #E(F_p) / n
+    const H: &'static str;
+
+    /// return the prime of the base field
+    fn p() -> BigUint {
+        from_hex(Self::P)
+    }
+
+    /// return the number as an element of the base field
+    fn field(num: BigUint) -> FF {
+        FF::new(num, Self::p())
+    }
+
+    /// return the coefficient a
+    fn a() -> FF {
+        Self::field(from_hex(Self::A))
+    }
+
+    /// return the coefficient b
+    fn b() -> FF {
+        Self::field(from_hex(Self::B))
+    }
+
+    /// return the generator point (x, y)
+    fn generator() -> (BigUint, BigUint) {
+        (from_hex(Self::GX), from_hex(Self::GY))
+    }
+
+    /// return the order of the generator point
+    fn order() -> BigUint {
+        from_hex(Self::N)
+    }
+
+    /// return the cofactor
+    fn cofactor() -> BigUint {
+        from_hex(Self::H)
+    }
+
+    /// return the GLV endomorphism if the curve has one, it is used for scalar multiplication
+    fn glv() -> Option<Glv> {
+        None
+    }
+}
+
+pub(crate) fn from_hex(s: &str) -> BigUint {
+    BigUint::from_str_radix(s, 16).expect("invalid hex string in curve parameters")
+}
+
+/// Point in the curve C
+///
+/// Points of different curves have different types so adding them does not compile
+///
+/// ```compile_fail
+/// use elliptic_curve::curve::{Curve, CurveParams};
+/// use elliptic_curve::secp256k1::Secp256k1;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Toy;
+///
+/// impl CurveParams for Toy {
+///     const NAME: &'static str = "toy";
+///     const P: &'static str = "61";
+///     const A: &'static str = "2";
+///     const B: &'static str = "3";
+///     const GX: &'static str = "0";
+///     const GY: &'static str = "a";
+///     const N: &'static str = "32";
+///     const H: &'static str = "2";
+/// }
+///
+/// let p = Secp256k1::new().g().clone();
+/// let q = Curve::<Toy>::new().g().clone();
+/// let _ = p + q;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct CurvePoint<C: CurveParams> {
+    point: ECCPoint,
+    _curve: PhantomData<C>,
+}
+
+impl<C: CurveParams> Point for CurvePoint<C> {
+    /// create a new point, panic if a and b are not the parameters of C
+    fn new(point: &PointData) -> Self {
+        Self::from_ecc(ECCPoint::new(point))
+    }
+
+    /// return x
+    fn x(&self) -> FF {
+        self.point.x()
+    }
+
+    /// return y
+    fn y(&self) -> FF {
+        self.point.y()
+    }
+
+    /// return (x, y)
+    fn xy(&self) -> (FF, FF) {
+        self.point.xy()
+    }
+}
+
+impl<C: CurveParams> CurvePoint<C> {
+    /// wrap an untyped point, panic if it is not a point of C
+    pub fn from_ecc(point: ECCPoint) -> Self {
+        if point.a != C::a() || point.b != C::b() {
+            panic!("not in the curve {}", C::NAME);
+        }
+        Self {
+            point,
+            _curve: PhantomData,
+        }
+    }
+
+    /// return infinity point
+    pub fn new_infinity_point() -> Self {
+        Self::from_ecc(ECCPoint::new_infinity_point(C::a(), C::b()))
+    }
+
+    /// return the underlying untyped point
+    pub fn as_ecc(&self) -> &ECCPoint {
+        &self.point
+    }
+
+    /// return the underlying untyped point
+    pub fn into_ecc(self) -> ECCPoint {
+        self.point
+    }
+
+    /// return true if the point is infinity
+    pub fn is_infinity(&self) -> bool {
+        self.point.is_infinity()
+    }
+
+    /// return n*P
+    pub fn scalar_mul(&self, n: BigUint) -> Self {
+        self * &n
+    }
+
+    /// return 2P
+    pub fn double(&self) -> Self {
+        Self {
+            point: self.point.double(),
+            _curve: PhantomData,
+        }
+    }
+
+    /// return k*P with w-NAF of window width w
+    pub fn wnaf_mul(&self, k: &BigUint, w: usize) -> Self {
+        Self {
+            point: wnaf_mul(&self.point, k, w),
+            _curve: PhantomData,
+        }
+    }
+
+    /// return k*P for a secret k with the constant time ladder
+    ///
+    /// k is reduced modulo the group order n*h so the ladder always runs over its bit
+    /// length, also for points outside the subgroup of G. Panic if n*h is even as the
+    /// complete formulas of the ladder only hold on curves of odd order
+    pub fn constant_time_mul(&self, k: &BigUint) -> Self {
+        let order = C::order() * C::cofactor();
+        assert!(
+            order.bit(0),
+            "the constant time ladder needs a curve of odd order"
+        );
+        let point = match C::glv() {
+            Some(glv) => glv.constant_time_mul(&self.point, k),
+            None => constant_time_mul(&self.point, &(k % &order), order.bits()),
+        };
+        Self {
+            point,
+            _curve: PhantomData,
+        }
+    }
+
+    /// return sum k_i*P_i, the infinity point if there is no point and None if the
+    /// lengths differ
+    pub fn msm(points: &[Self], scalars: &[BigUint]) -> Option<Self> {
+        if points.len() != scalars.len() {
+            return None;
+        }
+        if points.is_empty() {
+            return Some(Self::new_infinity_point());
+        }
+        let points: Vec<ECCPoint> = points.iter().map(|p| p.point.clone()).collect();
+        Some(Self {
+            point: msm(&points, scalars)?,
+            _curve: PhantomData,
+        })
+    }
+
+    /// return k1*P + k2*Q with Shamir's trick
+    pub fn shamir_mul(&self, k1: &BigUint, q: &Self, k2: &BigUint) -> Self {
+        let point = match C::glv() {
+            Some(glv) => glv.shamir_mul(&self.point, k1, &q.point, k2),
+            None => shamir_mul(&self.point, k1, &q.point, k2),
+        };
+        Self {
+            point,
+            _curve: PhantomData,
+        }
+    }
+
+    /// return h*P which is in the subgroup of order n
+    pub fn clear_cofactor(&self) -> Self {
+        Self {
+            point: &self.point * &C::cofactor(),
+            _curve: PhantomData,
+        }
+    }
+
+    /// encode the point in SEC1 format
+    pub fn to_bytes(&self, compressed: bool) -> Vec<u8> {
+        self.point.to_bytes(compressed)
+    }
+
+    /// decode a point in SEC1 format, return None if it is not a valid point of C
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let point = ECCPoint::from_bytes(bytes, &C::a(), &C::b())?;
+        Some(Self::from_ecc(point))
+    }
+}
+
+impl<C: CurveParams> Add for CurvePoint<C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl<C: CurveParams> Add<&Self> for CurvePoint<C> {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        Self {
+            point: self.point + &rhs.point,
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<C: CurveParams> AddAssign for CurvePoint<C> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.point += &rhs.point;
+    }
+}
+
+impl<C: CurveParams> AddAssign<&Self> for CurvePoint<C> {
+    fn add_assign(&mut self, rhs: &Self) {
+        self.point += &rhs.point;
+    }
+}
+
+impl<C: CurveParams> Neg for CurvePoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl<C: CurveParams> Neg for &CurvePoint<C> {
+    type Output = CurvePoint<C>;
+
+    fn neg(self) -> Self::Output {
+        CurvePoint {
+            point: -&self.point,
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<C: CurveParams> Sub for CurvePoint<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self - &rhs
+    }
+}
+
+impl<C: CurveParams> Sub<&Self> for CurvePoint<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        Self {
+            point: self.point - &rhs.point,
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<C: CurveParams> SubAssign for CurvePoint<C> {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.point -= &rhs.point;
+    }
+}
+
+impl<C: CurveParams> SubAssign<&Self> for CurvePoint<C> {
+    fn sub_assign(&mut self, rhs: &Self) {
+        self.point -= &rhs.point;
+    }
+}
+
+impl<C: CurveParams> Sum for CurvePoint<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new_infinity_point(), |acc, p| acc + &p)
+    }
+}
+
+impl<'a, C: CurveParams> Sum<&'a CurvePoint<C>> for CurvePoint<C> {
+    fn sum<I: Iterator<Item = &'a CurvePoint<C>>>(iter: I) -> Self {
+        iter.fold(Self::new_infinity_point(), |acc, p| acc + p)
+    }
+}
+
+impl<C: CurveParams> Mul<&BigUint> for &CurvePoint<C> {
+    type Output = CurvePoint<C>;
+
+    /// use the GLV endomorphism of C if there is one
+    fn mul(self, n: &BigUint) -> Self::Output {
+        let point = match C::glv() {
+            Some(glv) => glv.mul(&self.point, n),
+            None => &self.point * n,
+        };
+        CurvePoint {
+            point,
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<C: CurveParams> Mul<&BigUint> for CurvePoint<C> {
+    type Output = Self;
+
+    fn mul(self, n: &BigUint) -> Self::Output {
+        &self * n
+    }
+}
+
+impl<C: CurveParams> Mul<&Scalar<C>> for &CurvePoint<C> {
+    type Output = CurvePoint<C>;
+
+    fn mul(self, k: &Scalar<C>) -> Self::Output {
+        self * k.value()
+    }
+}
+
+impl<C: CurveParams> Mul<&Scalar<C>> for CurvePoint<C> {
+    type Output = Self;
+
+    fn mul(self, k: &Scalar<C>) -> Self::Output {
+        &self * k.value()
+    }
+}
+
+/// Short weierstrass curve built from the parameters C
+#[derive(Debug, Clone)]
+pub struct Curve<C: CurveParams> {
+    pub a: FF,
+    pub b: FF,
+    pub p: BigUint,
+    pub g: CurvePoint<C>,
+    pub n: BigUint,
+    pub h: BigUint,
+    g_table: OnceLock<FixedBaseTable>,
+}
+
+impl<C: CurveParams> Default for Curve<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: CurveParams> Curve<C> {
+    /// Create a new curve from the parameters C
+    pub fn new() -> Self {
+        let p = C::p();
+        let a = C::a();
+        let b = C::b();
+        let (x, y) = C::generator();
+        let g: PointData = (
+            Some(FF::new(x, p.clone())),
+            Some(FF::new(y, p.clone())),
+            a.clone(),
+            b.clone(),
+        );
+        let g = CurvePoint::new(&g);
+        Self {
+            a,
+            b,
+            p,
+            g,
+            n: C::order(),
+            h: C::cofactor(),
+            g_table: OnceLock::new(),
+        }
+    }
+
+    /// Return the name of the curve
+    pub fn name(&self) -> &'static str {
+        C::NAME
+    }
+
+    /// Return the generator point
+    pub fn g(&self) -> &CurvePoint<C> {
+        &self.g
+    }
+
+    /// return the n which is the order of the generator point
+    pub fn n(&self) -> &BigUint {
+        &self.n
+    }
+
+    /// return the cofactor h
+    pub fn h(&self) -> &BigUint {
+        &self.h
+    }
+
+    /// return k*G with the precomputed table of the generator, this is not constant time
+    ///
+    /// the table is built on the first call and kept in the curve. If g has been changed
+    /// since then, k*g is computed with w-NAF instead
+    pub fn mul_g(&self, k: &BigUint) -> CurvePoint<C> {
+        let table = self
+            .g_table
+            .get_or_init(|| FixedBaseTable::new(self.g.as_ecc(), self.n.bits(), 4));
+        if table.base() != self.g.as_ecc() {
+            return self.g.wnaf_mul(k, WNAF_WINDOW);
+        }
+        CurvePoint {
+            point: table.mul(k),
+            _curve: PhantomData,
+        }
+    }
+
+    /// return infinity point
+    pub fn infinity(&self) -> CurvePoint<C> {
+        CurvePoint::new_infinity_point()
+    }
+
+    /// create a new point
+    pub fn point(&self, x: BigUint, y: BigUint) -> CurvePoint<C> {
+        let p: PointData = (
+            Some(FF::new(x, self.p.clone())),
+            Some(FF::new(y, self.p.clone())),
+            self.a.clone(),
+            self.b.clone(),
+        );
+        CurvePoint::new(&p)
+    }
+
+    /// SEC1 public key validation, section 3.2.2.1
+    ///
+    /// Q is not infinity, x and y are in [0, p - 1], Q is in the curve and n*Q = O, so
+    /// a point of another curve (invalid curve attack) or of a small subgroup is rejected
+    pub fn validate_public_key(&self, q: &CurvePoint<C>) -> bool {
+        let q = q.as_ecc();
+        if q.is_infinity() || q.a != self.a || q.b != self.b || !q.is_on_curve() {
+            return false;
+        }
+        // untyped multiplication, the GLV path reduces the scalar modulo n
+        (q * &self.n).is_infinity()
+    }
+
+    /// decode a SEC1 public key and validate it
+    pub fn public_key_from_bytes(&self, bytes: &[u8]) -> Option<CurvePoint<C>> {
+        let q = CurvePoint::from_bytes(bytes)?;
+        self.validate_public_key(&q).then_some(q)
+    }
+
+    /// lift x to a point which mean return a point (x, y) such that y^2 = x^3 + ax + b mod p
+    ///
+    /// y is the odd root if odd is true and the even root otherwise, return None if x is
+    /// not below p or x^3 + ax + b has no square root
+    pub fn lift_x(&self, x: &BigUint, odd: bool) -> Option<CurvePoint<C>> {
+        if x >= &self.p {
+            return None;
+        }
+        let x = FF::new(x.clone(), self.p.clone());
+        let point = ECCPoint::lift_x(&x, odd, &self.a, &self.b)?;
+        Some(CurvePoint::from_ecc(point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::{Secp256k1, Secp256k1Params};
+    use crate::test_utils::Toy;
+
+    /// y^2 = x^3 + 2x + 16 over F_97 which has 82 = 2 * 41 points
+    #[derive(Debug, Clone, PartialEq)]
+    struct SmallCofactor;
+
+    impl CurveParams for SmallCofactor {
+        const NAME: &'static str = "small cofactor";
+        const P: &'static str = "61";
+        const A: &'static str = "2";
+        const B: &'static str = "10";
+        const GX: &'static str = "0";
+        const GY: &'static str = "4";
+        const N: &'static str = "29";
+        const H: &'static str = "2";
+    }
+
+    #[test]
+    fn test_validate_public_key() {
+        let e = Curve::<SmallCofactor>::new();
+        let g = e.g().clone();
+        assert!(e.validate_public_key(&g));
+        assert!(e.validate_public_key(&g.scalar_mul(7_u32.into())));
+        assert!(!e.validate_public_key(&e.infinity()));
+
+        // t has order 2 and q = g + t has order 82, both are outside the subgroup
+        let t = e.point(64_u32.into(), 0_u32.into());
+        let q = g.clone() + &t;
+        assert!(!e.validate_public_key(&t));
+        assert!(!e.validate_public_key(&q));
+        assert!(t.clear_cofactor().is_infinity());
+        assert_eq!(q.clear_cofactor(), g.double());
+        assert!(e.validate_public_key(&q.clear_cofactor()));
+
+        let bytes = q.to_bytes(true);
+        assert_eq!(CurvePoint::<SmallCofactor>::from_bytes(&bytes), Some(q));
+        assert_eq!(e.public_key_from_bytes(&bytes), None);
+        assert_eq!(e.public_key_from_bytes(&g.to_bytes(false)), Some(g));
+        assert_eq!(e.public_key_from_bytes(&[0x00]), None);
+    }
+
+    #[test]
+    fn test_invalid_curve_point() {
+        let secp256k1 = Secp256k1::new();
+        let g = secp256k1.g().as_ecc().clone();
+        let (x, y) = (g.x.clone().unwrap(), g.y.clone().unwrap());
+        let one = Secp256k1Params::field(1_u32.into());
+
+        // (x, y + 1) is in the curve y^2 = x^3 + b' with another b', the addition formulas
+        // do not use b so an unchecked scalar multiplication would happen in that curve
+        let invalid = CurvePoint::from_ecc(ECCPoint {
+            x: Some(x.clone()),
+            y: Some(y.clone() + &one),
+            a: g.a.clone(),
+            b: g.b.clone(),
+        });
+        assert!(!secp256k1.validate_public_key(&invalid));
+
+        // coordinates must be reduced modulo p
+        let unreduced = CurvePoint::from_ecc(ECCPoint {
+            x: Some(FF {
+                num: &x.num + &x.prime,
+                prime: x.prime.clone(),
+            }),
+            y: Some(y.clone()),
+            a: g.a.clone(),
+            b: g.b.clone(),
+        });
+        assert!(!secp256k1.validate_public_key(&unreduced));
+
+        let mut bytes = secp256k1.g().to_bytes(false);
+        bytes[64] ^= 1;
+        assert_eq!(secp256k1.public_key_from_bytes(&bytes), None);
+        assert!(secp256k1.validate_public_key(secp256k1.g()));
+    }
+
+    #[test]
+    fn test_curve_params() {
+        let toy = Curve::<Toy>::new();
+        assert_eq!(toy.name(), "toy");
+        assert_eq!(toy.p, BigUint::from(97_u32));
+        assert_eq!(toy.n(), &BigUint::from(50_u32));
+        assert_eq!(toy.h(), &BigUint::from(2_u32));
+        assert_eq!(
+            toy.g().xy(),
+            (Toy::field(0_u32.into()), Toy::field(10_u32.into()))
+        );
+        assert!(toy.g().scalar_mul(toy.n().clone()).is_infinity());
+        assert!(!toy.g().scalar_mul(BigUint::from(25_u32)).is_infinity());
+    }
+
+    #[test]
+    fn test_typed_addition() {
+        let toy = Curve::<Toy>::new();
+        let g = toy.g().clone();
+        let g3 = g.clone() + &g + g.clone();
+        assert_eq!(g3, g.scalar_mul(BigUint::from(3_u32)));
+        assert_eq!(g.clone() + toy.infinity(), g);
+        assert_eq!(g3.as_ecc(), &g.as_ecc().scalar_mul(BigUint::from(3_u32)));
+    }
+
+    #[test]
+    fn test_typed_operators() {
+        let toy = Curve::<Toy>::new();
+        let g = toy.g().clone();
+        let g2 = g.double();
+        assert_eq!(g2, g.clone() + &g);
+        assert_eq!(g2.clone() - &g, g);
+        assert_eq!(-(-g.clone()), g);
+        assert!((g.clone() - g.clone()).is_infinity());
+        assert_eq!(&g * &BigUint::from(7_u32), g.scalar_mul(7_u32.into()));
+        assert_eq!(g.clone() * &Scalar::from(7_u64), g.scalar_mul(7_u32.into()));
+        // scalars are reduced modulo n = 50
+        assert_eq!(&g * &Scalar::from(57_u64), &g * &BigUint::from(7_u32));
+
+        let mut acc = toy.infinity();
+        acc += &g;
+        acc += g2.clone();
+        acc -= &g;
+        assert_eq!(acc, g2);
+        acc -= g2.clone();
+        assert!(acc.is_infinity());
+
+        let points = [g.clone(), g2.clone(), -g2.clone()];
+        assert_eq!(points.iter().sum::<CurvePoint<Toy>>(), g);
+        assert!(Vec::<CurvePoint<Toy>>::new()
+            .into_iter()
+            .sum::<CurvePoint<Toy>>()
+            .is_infinity());
+    }
+
+    #[test]
+    fn test_mul_g_changed_generator() {
+        let mut toy = Curve::<Toy>::new();
+        let k = BigUint::from(7_u32);
+        assert_eq!(toy.mul_g(&k), toy.g().scalar_mul(k.clone()));
+        // the table of the old generator is not used any more
+        toy.g = toy.g().double();
+        assert_eq!(toy.mul_g(&k), toy.g().scalar_mul(k.clone()));
+    }
+
+    #[test]
+    fn test_fast_mul() {
+        let toy = Curve::<Toy>::new();
+        let g = toy.g().clone();
+        let q = g.double();
+        for k in 0..60_u32 {
+            let k = BigUint::from(k);
+            let expected = g.scalar_mul(k.clone());
+            assert_eq!(toy.mul_g(&k), expected);
+            assert_eq!(g.wnaf_mul(&k, 3), expected);
+            assert_eq!(
+                CurvePoint::msm(&[g.clone(), q.clone()], &[k.clone(), BigUint::from(3_u32)]),
+                Some(g.shamir_mul(&k, &q, &BigUint::from(3_u32)))
+            );
+            assert_eq!(
+                g.shamir_mul(&k, &q, &BigUint::from(3_u32)),
+                expected + &q * &BigUint::from(3_u32)
+            );
+        }
+    }
+
+    /// y^2 = x^3 + x + 13 over F_97 which has 93 = 3 * 31 points
+    #[derive(Debug, Clone, PartialEq)]
+    struct OddToy;
+
+    impl CurveParams for OddToy {
+        const NAME: &'static str = "odd toy";
+        const P: &'static str = "61";
+        const A: &'static str = "1";
+        const B: &'static str = "d";
+        const GX: &'static str = "f";
+        const GY: &'static str = "45";
+        const N: &'static str = "1f";
+        const H: &'static str = "3";
+    }
+
+    #[test]
+    fn test_constant_time_mul_cofactor() {
+        // every point of the curve, including the points outside the subgroup of G
+        let e = Curve::<OddToy>::new();
+        let mut points = vec![e.infinity()];
+        for x in 0..97_u32 {
+            let x = OddToy::field(x.into());
+            for odd in [false, true] {
+                if let Some(p) = ECCPoint::lift_x(&x, odd, &e.a, &e.b) {
+                    points.push(CurvePoint::from_ecc(p));
+                }
+            }
+        }
+        points.dedup();
+        assert_eq!(points.len(), 93);
+        for p in &points {
+            for k in 0..=100_u32 {
+                let k = BigUint::from(k);
+                assert_eq!(p.constant_time_mul(&k), p.scalar_mul(k.clone()));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the constant time ladder needs a curve of odd order")]
+    fn test_constant_time_mul_even_order() {
+        let toy = Curve::<Toy>::new();
+        toy.g().constant_time_mul(&BigUint::from(3_u32));
+    }
+
+    #[test]
+    fn test_empty_msm() {
+        assert!(CurvePoint::<Toy>::msm(&[], &[]).unwrap().is_infinity());
+        let g = Curve::<Toy>::new().g().clone();
+        assert_eq!(
+            CurvePoint::msm(&[g.clone(), g], &[BigUint::from(1_u32)]),
+            None
+        );
+        assert_eq!(CurvePoint::<Toy>::msm(&[], &[BigUint::from(1_u32)]), None);
+    }
+
+    #[test]
+    fn test_lift_x() {
+        // p = 97 = 1 mod 4 so the square root needs tonelli-shanks
+        let toy = Curve::<Toy>::new();
+        let g = toy.g().clone();
+        assert_eq!(toy.lift_x(&BigUint::from(0_u32), false), Some(g.clone()));
+        let odd = toy.lift_x(&BigUint::from(0_u32), true).unwrap();
+        assert_eq!(odd.y(), Toy::field(87_u32.into()));
+        assert!((odd + g).is_infinity());
+        // 2^3 + 2*2 + 3 = 15 is not a square modulo 97
+        assert_eq!(toy.lift_x(&BigUint::from(2_u32), false), None);
+        assert_eq!(toy.lift_x(&BigUint::from(97_u32), false), None);
+    }
+
+    #[test]
+    fn test_sec1_encoding() {
+        let toy = Curve::<Toy>::new();
+        let g3 = toy.g().scalar_mul(BigUint::from(3_u32));
+        for compressed in [true, false] {
+            let bytes = g3.to_bytes(compressed);
+            assert_eq!(CurvePoint::<Toy>::from_bytes(&bytes), Some(g3.clone()));
+        }
+        assert_eq!(g3.to_bytes(false).len(), 3);
+        assert_eq!(CurvePoint::<Toy>::from_bytes(&[0x00]), Some(toy.infinity()));
+        // a secp256k1 point is not a point of the toy curve
+        let g = Secp256k1::new().g().to_bytes(true);
+        assert_eq!(CurvePoint::<Toy>::from_bytes(&g), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_other_curve() {
+        let g = Secp256k1::new().g().clone();
+        CurvePoint::<Toy>::from_ecc(g.into_ecc());
+    }
+}
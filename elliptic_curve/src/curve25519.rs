@@ -0,0 +1,166 @@
+use crate::ed25519::Ed25519Params;
+use crate::montgomery::{BirationalEdwards, MontgomeryParams, MontgomeryPoint, Xdh};
+
+/// Parameters of Curve25519, y^2 = x^3 + 486662*x^2 + x over F_p with p = 2^255 - 19
+/// https://www.rfc-editor.org/rfc/rfc7748#section-4.1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Curve25519Params;
+
+impl MontgomeryParams for Curve25519Params {
+    const NAME: &'static str = "curve25519";
+    const P: &'static str = "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed";
+    const A: &'static str = "76d06";
+    const B: &'static str = "1";
+    const GU: &'static str = "9";
+    const GV: &'static str = "20ae19a1b8a086b4e01edd2c7748d14c923d4d7e6d7c61b229e9c5a27eced3d9";
+    const N: &'static str = "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+    const H: &'static str = "8";
+    const BITS: usize = 255;
+}
+
+impl BirationalEdwards for Curve25519Params {
+    type Edwards = Ed25519Params;
+
+    /// c = sqrt(-486664)
+    const C: &'static str = "70d9120b9f5ff9442d84f723fc03b0813a5e2c2eb482e57d3391fb5500ba81e7";
+}
+
+/// Point in Curve25519
+pub type Curve25519Point = MontgomeryPoint<Curve25519Params>;
+
+/// X25519 key exchange
+pub type X25519 = Xdh<Curve25519Params>;
+
+/// the X25519 function of RFC 7748
+pub fn x25519(k: [u8; 32], u: [u8; 32]) -> [u8; 32] {
+    let mut out = [0_u8; 32];
+    out.copy_from_slice(&X25519::xdh(&k, &u));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edwards::EdwardsPoint;
+    use crate::test_utils::{hex, hex_array};
+    use num_bigint::BigUint;
+
+    /// https://www.rfc-editor.org/rfc/rfc7748#section-5.2
+    #[test]
+    fn test_x25519() {
+        let vectors = [
+            (
+                "a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4",
+                "e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c",
+                "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552",
+            ),
+            (
+                "4b66e9d4d1b4673c5ad22691957d6af5c11b6421e0ea01d42ca4169e7918ba0d",
+                "e5210f12786811d3f4b7959d0538ae2c31dbe7106fc03c3efc4cd549c715a493",
+                "95cbde9476e8907d7aade45cb4b873f88b595a68799fa152e6f8f7647aac7957",
+            ),
+        ];
+        for (k, u, out) in vectors {
+            assert_eq!(x25519(hex_array(k), hex_array(u)), hex_array(out));
+        }
+        // first step of the iterated test
+        let nine = hex_array("0900000000000000000000000000000000000000000000000000000000000000");
+        assert_eq!(
+            x25519(nine, nine),
+            hex_array("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+        );
+    }
+
+    /// k after n iterations of k, u = x25519(k, u), k starting from the base point
+    fn x25519_iterated(n: usize) -> [u8; 32] {
+        let mut k = hex_array("0900000000000000000000000000000000000000000000000000000000000000");
+        let mut u = k;
+        for _ in 0..n {
+            (k, u) = (x25519(k, u), k);
+        }
+        k
+    }
+
+    /// https://www.rfc-editor.org/rfc/rfc7748#section-5.2
+    #[test]
+    fn test_x25519_iterated() {
+        assert_eq!(
+            x25519_iterated(1),
+            hex_array("422c8e7a6227d7bca1350b3e2bb7279f7897b87bb6854b783c60e80311ae3079")
+        );
+        assert_eq!(
+            x25519_iterated(1000),
+            hex_array("684cf59ba83309552800ef566f2f4d3c1c3887c49360e3875f2eb94d99532c51")
+        );
+    }
+
+    /// takes hours even in release builds, run with --ignored
+    #[test]
+    #[ignore]
+    fn test_x25519_iterated_1m() {
+        assert_eq!(
+            x25519_iterated(1_000_000),
+            hex_array("7c3911e0ab2586fd864497297e575e6f3bc601c0883c30df5f4dd2d24f665424")
+        );
+    }
+
+    /// https://www.rfc-editor.org/rfc/rfc7748#section-6.1
+    #[test]
+    fn test_diffie_hellman() {
+        let alice = X25519::new(hex(
+            "77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a",
+        ));
+        let bob = X25519::new(hex(
+            "5dab087e624a8a4b79e17f8b83800ee66f3bb1292618b6fd1c2f8b27ff88e0eb",
+        ));
+        assert_eq!(
+            alice.to_bytes(),
+            hex("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a")
+        );
+        assert!(!format!("{alice:?}").contains("secret"));
+        assert_eq!(
+            alice.pub_key,
+            hex("8520f0098930a754748b7ddcb43ef75a0dbf3a0d26381af4eba4a98eaa9b4e6a")
+        );
+        assert_eq!(
+            bob.pub_key,
+            hex("de9edb7d7b7dc1b4d35b61c2ece435373f8343c85b78674dadfc7e146f882b4f")
+        );
+        let shared = hex("4a5d9d5ba4ce2de1728e3bf480350f25e07e21c947d19e3376f09b3c1e161742");
+        assert_eq!(alice.diffie_hellman(&bob.pub_key), Some(shared.clone()));
+        assert_eq!(bob.diffie_hellman(&alice.pub_key), Some(shared));
+
+        let (alice, bob) = (X25519::generate(), X25519::generate());
+        assert_eq!(
+            alice.diffie_hellman(&bob.pub_key),
+            bob.diffie_hellman(&alice.pub_key)
+        );
+        // u = 0 has small order so the shared secret is all zero
+        assert_eq!(alice.diffie_hellman(&[0_u8; 32]), None);
+    }
+
+    #[test]
+    fn test_curve25519_points() {
+        let g = Curve25519Point::generator();
+        let g2 = g.clone() + &g;
+        let g3 = g2.clone() + &g;
+        assert_eq!(g3.clone() + (-g.clone()), g2);
+        assert!((g.clone() + (-g.clone())).is_infinity());
+        assert_eq!(g3.x.clone().unwrap().num, g.ladder(&BigUint::from(3_u32)));
+        assert_eq!(g.ladder(&Curve25519Params::order()), BigUint::from(0_u32));
+    }
+
+    #[test]
+    fn test_birational_map() {
+        let g = Curve25519Point::generator();
+        let b = EdwardsPoint::<Ed25519Params>::generator();
+        assert_eq!(g.to_edwards(), b);
+        assert_eq!((g.clone() + &g).to_edwards(), b.double());
+        assert_eq!(Curve25519Point::from_edwards(&b.double()), g.clone() + &g);
+        assert!(Curve25519Point::new_infinity_point()
+            .to_edwards()
+            .is_identity());
+        let t = Curve25519Point::new(BigUint::from(0_u32), BigUint::from(0_u32));
+        assert_eq!(Curve25519Point::from_edwards(&t.to_edwards()), t);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::montgomery::{MontgomeryParams, MontgomeryPoint, Xdh};
+
+/// Parameters of Curve448, y^2 = x^3 + 156326*x^2 + x over F_p with p = 2^448 - 2^224 - 1
+/// https://www.rfc-editor.org/rfc/rfc7748#section-4.2
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Curve448Params;
+
+impl MontgomeryParams for Curve448Params {
+    const NAME: &'static str = "curve448";
+    const P: &'static str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+    const A: &'static str = "262a6";
+    const B: &'static str = "1";
+    const GU: &'static str = "5";
+    const GV: &'static str = "7d235d1295f5b1f66c98ab6e58326fcecbae5d34f55545d060f75dc28df3f6edb8027e2346430d211312c4b150677af76fd7223d457b5b1a";
+    const N: &'static str = "3fffffffffffffffffffffffffffffffffffffffffffffffffffffff7cca23e9c44edb49aed63690216cc2728dc58f552378c292ab5844f3";
+    const H: &'static str = "4";
+    const BITS: usize = 448;
+}
+
+/// Point in Curve448
+pub type Curve448Point = MontgomeryPoint<Curve448Params>;
+
+/// X448 key exchange
+pub type X448 = Xdh<Curve448Params>;
+
+/// the X448 function of RFC 7748
+pub fn x448(k: [u8; 56], u: [u8; 56]) -> [u8; 56] {
+    let mut out = [0_u8; 56];
+    out.copy_from_slice(&X448::xdh(&k, &u));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex_array;
+    use num_bigint::BigUint;
+
+    /// https://www.rfc-editor.org/rfc/rfc7748#section-5.2
+    #[test]
+    fn test_x448() {
+        let k = hex_array("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3");
+        let u = hex_array("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086");
+        let out = hex_array("ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f");
+        assert_eq!(x448(k, u), out);
+
+        // first step of the iterated test
+        let mut five = [0_u8; 56];
+        five[0] = 5;
+        assert_eq!(x448(five, five), hex_array("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113"));
+    }
+
+    /// k after n iterations of k, u = x448(k, u), k starting from the base point
+    fn x448_iterated(n: usize) -> [u8; 56] {
+        let mut k = [0_u8; 56];
+        k[0] = 5;
+        let mut u = k;
+        for _ in 0..n {
+            (k, u) = (x448(k, u), k);
+        }
+        k
+    }
+
+    /// https://www.rfc-editor.org/rfc/rfc7748#section-5.2
+    #[test]
+    fn test_x448_iterated() {
+        assert_eq!(x448_iterated(1), hex_array("3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113"));
+        assert_eq!(x448_iterated(1000), hex_array("aa3b4749d55b9daf1e5b00288826c467274ce3ebbdd5c17b975e09d4af6c67cf10d087202db88286e2b79fceea3ec353ef54faa26e219f38"));
+    }
+
+    /// takes hours even in release builds, run with --ignored
+    #[test]
+    #[ignore]
+    fn test_x448_iterated_1m() {
+        assert_eq!(x448_iterated(1_000_000), hex_array("077f453681caca3693198420bbe515cae0002472519b3e67661a7e89cab94695c8f4bcd66e61b9b9c946da8d524de3d69bd9d9d66b997e37"));
+    }
+
+    #[test]
+    fn test_diffie_hellman() {
+        let (alice, bob) = (X448::generate(), X448::generate());
+        assert_eq!(alice.pub_key.len(), 56);
+        assert_eq!(
+            alice.diffie_hellman(&bob.pub_key),
+            bob.diffie_hellman(&alice.pub_key)
+        );
+    }
+
+    #[test]
+    fn test_curve448_points() {
+        let g = Curve448Point::generator();
+        let g2 = g.clone() + &g;
+        assert_eq!(g2.x.unwrap().num, g.ladder(&BigUint::from(2_u32)));
+        assert_eq!(g.ladder(&Curve448Params::order()), BigUint::from(0_u32));
+    }
+}
@@ -0,0 +1,94 @@
+use crate::curve::CurveParams;
+use crate::ecdsa::{SigningKey, VerifyingKey};
+use crate::point::{to_fixed_bytes, Point};
+use hkdf::Hkdf;
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+/// Shared secret of ECDH, the x coordinate of d*Q as big endian bytes of the length of p
+///
+/// the raw secret is not uniform, derive keys from it with derive_key, it is zeroized on drop
+pub struct SharedSecret {
+    bytes: Vec<u8>,
+}
+
+impl SharedSecret {
+    /// return the raw x coordinate, the Z of SEC1 section 3.3.1
+    pub fn raw_secret_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// derive len bytes with HKDF-SHA-256 of RFC 5869, panic if len > 255 * 32
+    pub fn derive_key(&self, salt: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+        let mut okm = vec![0_u8; len];
+        Hkdf::<Sha256>::new(Some(salt), &self.bytes)
+            .expand(info, &mut okm)
+            .expect("HKDF output is at most 255 * 32 bytes");
+        okm
+    }
+}
+
+impl Drop for SharedSecret {
+    fn drop(&mut self) {
+        self.bytes.zeroize();
+    }
+}
+
+/// Elliptic curve Diffie-Hellman between a private key and the public key of the peer
+///
+/// the public key is validated on construction so d*Q is never the point at infinity
+pub fn diffie_hellman<C: CurveParams>(
+    secret: &SigningKey<C>,
+    public: &VerifyingKey<C>,
+) -> SharedSecret {
+    let shared = public.as_point().constant_time_mul(&secret.secret_scalar());
+    let len = C::p().bits().div_ceil(8) as usize;
+    SharedSecret {
+        bytes: to_fixed_bytes(&shared.x().num, len),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p256::P256Params;
+    use crate::secp256k1::Secp256k1Params;
+    use crate::test_utils::hex;
+
+    #[test]
+    fn test_diffie_hellman() {
+        let mut rng = rand::thread_rng();
+        let alice = SigningKey::<Secp256k1Params>::random(&mut rng);
+        let bob = SigningKey::<Secp256k1Params>::random(&mut rng);
+        let ab = diffie_hellman(&alice, bob.verifying_key());
+        let ba = diffie_hellman(&bob, alice.verifying_key());
+        assert_eq!(ab.raw_secret_bytes(), ba.raw_secret_bytes());
+        assert_eq!(ab.raw_secret_bytes().len(), 32);
+        assert_eq!(
+            ab.derive_key(b"salt", b"info", 42),
+            ba.derive_key(b"salt", b"info", 42)
+        );
+        assert_ne!(
+            ab.derive_key(b"salt", b"info", 32),
+            ab.derive_key(b"salt", b"other", 32)
+        );
+    }
+
+    #[test]
+    fn test_diffie_hellman_cavs() {
+        // NIST CAVS ECC CDH primitive, P-256 count 0
+        let peer = VerifyingKey::<P256Params>::from_sec1_bytes(&hex(
+            "04700c48f77f56584c5cc632ca65640db91b6bacce3a4df6b42ce7cc838833d287\
+             db71e509e3fd9b060ddb20ba5c51dcc5948d46fbf640dfe0441782cab85fa4ac",
+        ))
+        .unwrap();
+        let key = SigningKey::<P256Params>::from_bytes(&hex(
+            "7d7dc5f71eb29ddaf80d6214632eeae03d9058af1fb6d22ed80badb62bc1a534",
+        ))
+        .unwrap();
+        assert_eq!(
+            diffie_hellman(&key, &peer).raw_secret_bytes(),
+            hex("46fc62106420ff012e54a434fbdd2d25ccc5852060561e68040dd7778997bd7b")
+        );
+    }
+}
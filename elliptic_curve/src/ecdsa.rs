@@ -1,65 +1,695 @@
-use crate::point::ECCPoint;
-use crate::point::Point;
-use crate::secp256k1::Secp256k1;
-use num_bigint::{BigUint, RandomBits};
-use rand::Rng;
-use sha2::{Digest, Sha256};
-
-pub struct Ecdsa {
-    pub e: Secp256k1,
+use crate::curve::{Curve, CurveParams, CurvePoint};
+use crate::point::{to_fixed_bytes, ECCPoint, Point};
+use crate::rfc6979::{bits2int, Rfc6979};
+use crate::secp256k1::Secp256k1Params;
+use crate::signature::{Signer, Verifier};
+use digest::core_api::BlockSizeUser;
+use digest::Digest;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use sha2::Sha256;
+use std::marker::PhantomData;
+use zeroize::Zeroize;
+
+/// ECDSA over the curve C, secp256k1 by default
+///
+/// it holds both keys, use SigningKey and VerifyingKey to keep them apart
+pub struct Ecdsa<C: CurveParams = Secp256k1Params> {
+    pub e: Curve<C>,
     pub d: BigUint,
-    pub pub_key: ECCPoint,
+    pub pub_key: CurvePoint<C>,
 }
 
-impl Ecdsa {
-    /// Create a new ECDSA with private key d
-    pub fn new(d: BigUint) -> Self {
-        let e = Secp256k1::new();
-        let pub_key = e.g().scalar_mul(d.clone());
-        Self { e, d, pub_key }
+impl<C: CurveParams> Ecdsa<C> {
+    /// Create a new ECDSA with private key d, return None if d is not in [1, n - 1]
+    ///
+    /// the public key is computed with the constant time ladder since d is secret
+    pub fn new(d: BigUint) -> Option<Self> {
+        let e = Curve::<C>::new();
+        if d.is_zero() || &d >= e.n() {
+            return None;
+        }
+        let pub_key = e.g().constant_time_mul(&d);
+        Some(Self { e, d, pub_key })
+    }
+
+    /// return the public key, which can verify without the private key
+    pub fn verifying_key(&self) -> VerifyingKey<C> {
+        VerifyingKey {
+            point: self.pub_key.clone(),
+        }
     }
 
     /// Hash a message
+    ///
+    /// the SHA-256 digest is truncated to the bit length of n as in FIPS 186-4
     pub fn hash(&self, m: &str) -> BigUint {
-        let mut hasher = Sha256::new();
-        hasher.update(m.as_bytes());
-        let result = hasher.finalize();
-        BigUint::from_bytes_be(&result)
+        bits2int(&Sha256::digest(m.as_bytes()), self.e.n().bits())
     }
 
     /// Sign a message
-    pub fn sign(&self, m: &str) -> (BigUint, BigUint) {
-        let mut rng = rand::thread_rng();
-        let z = self.hash(m);
-        let k: BigUint = rng.sample(RandomBits::new(256));
-        let r = self.e.g().scalar_mul(k.clone()).x().num;
-        let k_inv = k.modpow(&(self.e.n() - BigUint::from(2_u32)), self.e.n());
-        let s = (&z + &r * &self.d) * k_inv % self.e.n();
-        (r, s)
+    ///
+    /// the nonce k is derived from d and the hash with RFC 6979 so signing is deterministic,
+    /// every signing method returns the low s
+    pub fn sign(&self, m: &str) -> Signature<C> {
+        self.sign_with_entropy(m, &[])
+    }
+
+    /// Sign a message with the RFC 6979 nonce mixed with extra entropy
+    ///
+    /// the signature is randomized by extra but k stays secret even if extra is not
+    pub fn sign_with_entropy(&self, m: &str, extra: &[u8]) -> Signature<C> {
+        let digest = Sha256::digest(m.as_bytes());
+        sign_deterministic::<C, Sha256>(&self.e, &self.d, &digest, extra).0
+    }
+
+    /// Sign a message hashed with D, the nonces use HMAC over D
+    pub fn sign_digest<D: Digest + BlockSizeUser>(&self, m: &[u8]) -> Signature<C> {
+        sign_deterministic::<C, D>(&self.e, &self.d, &D::digest(m), &[]).0
+    }
+
+    /// Sign an already computed digest of a message
+    pub fn sign_prehash(&self, digest: &[u8]) -> Signature<C> {
+        sign_deterministic::<C, Sha256>(&self.e, &self.d, digest, &[]).0
+    }
+
+    /// Sign the hash z of a message with the nonce k
+    ///
+    /// return None if r = 0 or s = 0, then another k must be chosen
+    ///
+    /// k*G uses the constant time ladder, verify keeps the faster variable time path
+    pub fn sign_hash(&self, z: &BigUint, k: &BigUint) -> Option<Signature<C>> {
+        sign_with_k(&self.e, &self.d, z, k).map(|(sig, _)| sig)
     }
 
     /// Verify a signature
-    pub fn verify(&self, m: &str, r: &BigUint, s: &BigUint) -> bool {
+    pub fn verify(&self, m: &str, sig: &Signature<C>) -> bool {
         let z = self.hash(m);
-        let s_inv = s.modpow(&(self.e.n() - BigUint::from(2_u32)), self.e.n());
-        let u1 = z.clone() * s_inv.clone() % self.e.n();
-        let u2 = r.clone() * s_inv.clone() % self.e.n();
-        let p = self.e.g().scalar_mul(u1) + self.pub_key.clone().scalar_mul(u2);
-        r == &(p.x().num % self.e.n())
+        self.verify_hash(&z, sig)
+    }
+
+    /// Verify a signature of a message hashed with D
+    pub fn verify_digest<D: Digest>(&self, m: &[u8], sig: &Signature<C>) -> bool {
+        self.verify_prehash(&D::digest(m), sig)
+    }
+
+    /// Verify a signature of an already computed digest of a message
+    pub fn verify_prehash(&self, digest: &[u8], sig: &Signature<C>) -> bool {
+        self.verify_hash(&bits2int(digest, self.e.n().bits()), sig)
+    }
+
+    /// Verify a signature of the hash z of a message, both s and n - s are accepted
+    pub fn verify_hash(&self, z: &BigUint, sig: &Signature<C>) -> bool {
+        verify_with_key(&self.e, &self.pub_key, z, &sig.r, &sig.s)
+    }
+
+    /// Verify a signature, reject a high s
+    pub fn verify_strict(&self, m: &str, sig: &Signature<C>) -> bool {
+        sig.is_low_s() && self.verify(m, sig)
     }
 }
 
-#[cfg(test)]
+/// ECDSA signature (r, s) over the curve C
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Signature<C: CurveParams = Secp256k1Params> {
+    r: BigUint,
+    s: BigUint,
+    _curve: PhantomData<C>,
+}
+
+impl<C: CurveParams> Signature<C> {
+    /// create a signature, return None if r or s is not in [1, n - 1]
+    pub fn new(r: BigUint, s: BigUint) -> Option<Self> {
+        let n = C::order();
+        if r.is_zero() || s.is_zero() || r >= n || s >= n {
+            return None;
+        }
+        Some(Self {
+            r,
+            s,
+            _curve: PhantomData,
+        })
+    }
+
+    /// return r
+    pub fn r(&self) -> &BigUint {
+        &self.r
+    }
+
+    /// return s
+    pub fn s(&self) -> &BigUint {
+        &self.s
+    }
+
+    /// return true if s <= n / 2
+    pub fn is_low_s(&self) -> bool {
+        self.s <= C::order() >> 1
+    }
+
+    /// return (r, n - s) if s > n / 2 and the signature itself otherwise
+    ///
+    /// (r, s) and (r, n - s) are both valid, BIP 62 and BIP 146 only allow the low one
+    /// so a third party cannot change the signature
+    pub fn normalize_s(&self) -> Self {
+        if self.is_low_s() {
+            return self.clone();
+        }
+        Self {
+            r: self.r.clone(),
+            s: C::order() - &self.s,
+            _curve: PhantomData,
+        }
+    }
+
+    /// encode the signature as the ASN.1 DER SEQUENCE { r INTEGER, s INTEGER }
+    pub fn to_der(&self) -> Vec<u8> {
+        let mut content = der_integer(&self.r);
+        content.extend(der_integer(&self.s));
+        let mut der = vec![0x30];
+        der.extend(der_length(content.len()));
+        der.extend(content);
+        der
+    }
+
+    /// decode a DER signature
+    ///
+    /// return None for anything but the canonical encoding: long form lengths of short
+    /// contents, integers with extra leading zeros or negative, trailing data
+    pub fn from_der(bytes: &[u8]) -> Option<Self> {
+        let (tag, rest) = bytes.split_first()?;
+        if *tag != 0x30 {
+            return None;
+        }
+        let (content, rest) = parse_der_length(rest)?;
+        if !rest.is_empty() {
+            return None;
+        }
+        let (r, content) = parse_der_integer(content)?;
+        let (s, content) = parse_der_integer(content)?;
+        if !content.is_empty() {
+            return None;
+        }
+        Self::new(r, s)
+    }
+
+    /// encode the signature as r || s, each on the byte length of n
+    pub fn to_compact(&self) -> Vec<u8> {
+        let len = scalar_len::<C>();
+        let mut bytes = to_fixed_bytes(&self.r, len);
+        bytes.extend(to_fixed_bytes(&self.s, len));
+        bytes
+    }
+
+    /// decode a compact signature r || s, return None if the length or a value is wrong
+    pub fn from_compact(bytes: &[u8]) -> Option<Self> {
+        let len = scalar_len::<C>();
+        if bytes.len() != 2 * len {
+            return None;
+        }
+        let (r, s) = bytes.split_at(len);
+        Self::new(BigUint::from_bytes_be(r), BigUint::from_bytes_be(s))
+    }
+
+    /// encode the signature as r || s || recovery id, 65 bytes on secp256k1
+    pub fn to_recoverable_bytes(&self, recid: RecoveryId) -> Vec<u8> {
+        let mut bytes = self.to_compact();
+        bytes.push(recid.to_byte());
+        bytes
+    }
+
+    /// decode r || s || recovery id, the recovery id must be in [0, 3]
+    pub fn from_recoverable_bytes(bytes: &[u8]) -> Option<(Self, RecoveryId)> {
+        let (recid, compact) = bytes.split_last()?;
+        Some((Self::from_compact(compact)?, RecoveryId::from_byte(*recid)?))
+    }
+}
+
+/// byte length of the scalars of C
+fn scalar_len<C: CurveParams>() -> usize {
+    C::order().bits().div_ceil(8) as usize
+}
+
+/// DER length, short form below 128 and long form otherwise
+fn der_length(len: usize) -> Vec<u8> {
+    if len < 0x80 {
+        return vec![len as u8];
+    }
+    let bytes: Vec<u8> = len
+        .to_be_bytes()
+        .into_iter()
+        .skip_while(|b| *b == 0)
+        .collect();
+    let mut out = vec![0x80 | bytes.len() as u8];
+    out.extend(bytes);
+    out
+}
+
+/// DER INTEGER of a non negative number, a zero byte is prepended if the high bit is set
+fn der_integer(n: &BigUint) -> Vec<u8> {
+    let mut bytes = n.to_bytes_be();
+    if bytes[0] & 0x80 != 0 {
+        bytes.insert(0, 0);
+    }
+    let mut out = vec![0x02];
+    out.extend(der_length(bytes.len()));
+    out.extend(bytes);
+    out
+}
+
+/// split a DER length and the content it covers from the rest
+fn parse_der_length(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    let (first, rest) = bytes.split_first()?;
+    let (len, rest) = match *first {
+        len @ 0..=0x7f => (len as usize, rest),
+        // a signature is shorter than 64 KiB, the long form must not fit the short one
+        0x81 => {
+            let (len, rest) = rest.split_first()?;
+            if *len < 0x80 {
+                return None;
+            }
+            (*len as usize, rest)
+        }
+        0x82 => {
+            let (len, rest) = (rest.get(..2)?, &rest[2..]);
+            let len = u16::from_be_bytes([len[0], len[1]]) as usize;
+            if len < 0x100 {
+                return None;
+            }
+            (len, rest)
+        }
+        _ => return None,
+    };
+    (rest.len() >= len).then(|| rest.split_at(len))
+}
+
+/// parse a DER INTEGER which must be positive and minimally encoded
+fn parse_der_integer(bytes: &[u8]) -> Option<(BigUint, &[u8])> {
+    let (tag, rest) = bytes.split_first()?;
+    if *tag != 0x02 {
+        return None;
+    }
+    let (content, rest) = parse_der_length(rest)?;
+    match content {
+        [] => return None,
+        [b, ..] if b & 0x80 != 0 => return None,
+        [0, b, ..] if b & 0x80 == 0 => return None,
+        _ => {}
+    }
+    Some((BigUint::from_bytes_be(content), rest))
+}
+
+/// How the s of an ECDSA signature is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EcdsaVerification {
+    /// accept s and n - s as FIPS 186-4
+    Standard,
+    /// reject s > n / 2 as BIP 146
+    LowS,
+}
+
+/// ECDSA private key over the curve C
+///
+/// the secret is stored as bytes which are zeroized on drop, the BigUint used during
+/// signing are temporary
+pub struct SigningKey<C: CurveParams = Secp256k1Params> {
+    secret: Vec<u8>,
+    verifying_key: VerifyingKey<C>,
+}
+
+impl<C: CurveParams> SigningKey<C> {
+    /// create a signing key from the big endian secret, return None if it is not in [1, n - 1]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let n = C::order();
+        let d = BigUint::from_bytes_be(bytes);
+        if d.is_zero() || d >= n {
+            return None;
+        }
+        let e = Curve::<C>::new();
+        let point = e.g().constant_time_mul(&d);
+        Some(Self {
+            secret: to_fixed_bytes(&d, n.bits().div_ceil(8) as usize),
+            verifying_key: VerifyingKey { point },
+        })
+    }
+
+    /// create a random signing key
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let d = rng.gen_biguint_range(&BigUint::one(), &C::order());
+        Self::from_bytes(&d.to_bytes_be()).unwrap()
+    }
+
+    /// return the secret as big endian bytes of the length of n
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.secret.clone()
+    }
+
+    /// return the public key
+    pub fn verifying_key(&self) -> &VerifyingKey<C> {
+        &self.verifying_key
+    }
+
+    /// return the secret scalar d, used by ECDH
+    pub(crate) fn secret_scalar(&self) -> BigUint {
+        BigUint::from_bytes_be(&self.secret)
+    }
+
+    /// sign the digest of a message with a RFC 6979 nonce over HMAC-SHA-256
+    ///
+    /// the digest is truncated to the bit length of n and s is normalized to the low s
+    pub fn sign_prehash(&self, digest: &[u8]) -> Signature<C> {
+        self.sign_prehash_recoverable(digest).0
+    }
+
+    /// sign msg hashed with D, the nonces use HMAC over D
+    pub fn sign_digest<D: Digest + BlockSizeUser>(&self, msg: &[u8]) -> Signature<C> {
+        self.sign_digest_recoverable::<D>(msg).0
+    }
+
+    /// sign msg hashed with D and return the recovery id
+    ///
+    /// Ethereum tools sign the Keccak-256 digest with HMAC-SHA-256 nonces, use
+    /// sign_prehash_recoverable to get the same signatures
+    pub fn sign_digest_recoverable<D: Digest + BlockSizeUser>(
+        &self,
+        msg: &[u8],
+    ) -> (Signature<C>, RecoveryId) {
+        self.sign_recoverable_with::<D>(&D::digest(msg))
+    }
+
+    /// sign the SHA-256 hash of msg and return the recovery id of the signature
+    pub fn sign_recoverable(&self, msg: &[u8]) -> (Signature<C>, RecoveryId) {
+        self.sign_prehash_recoverable(&Sha256::digest(msg))
+    }
+
+    /// sign the digest of a message and return the recovery id of the signature
+    pub fn sign_prehash_recoverable(&self, digest: &[u8]) -> (Signature<C>, RecoveryId) {
+        self.sign_recoverable_with::<Sha256>(digest)
+    }
+
+    fn sign_recoverable_with<D: Digest + BlockSizeUser>(
+        &self,
+        digest: &[u8],
+    ) -> (Signature<C>, RecoveryId) {
+        let e = Curve::<C>::new();
+        let d = self.secret_scalar();
+        sign_deterministic::<C, D>(&e, &d, digest, &[])
+    }
+}
+
+impl<C: CurveParams> Drop for SigningKey<C> {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl<C: CurveParams> Signer<Signature<C>> for SigningKey<C> {
+    /// sign the SHA-256 hash of msg
+    fn sign(&self, msg: &[u8]) -> Signature<C> {
+        self.sign_prehash(&Sha256::digest(msg))
+    }
+}
+
+/// ECDSA public key over the curve C
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifyingKey<C: CurveParams = Secp256k1Params> {
+    point: CurvePoint<C>,
+}
+
+impl<C: CurveParams> VerifyingKey<C> {
+    /// create a verifying key from a point, return None if it is not a valid public key
+    pub fn from_point(point: CurvePoint<C>) -> Option<Self> {
+        Curve::<C>::new()
+            .validate_public_key(&point)
+            .then_some(Self { point })
+    }
+
+    /// decode a SEC1 public key, return None if it is not a valid public key
+    pub fn from_sec1_bytes(bytes: &[u8]) -> Option<Self> {
+        let point = Curve::<C>::new().public_key_from_bytes(bytes)?;
+        Some(Self { point })
+    }
+
+    /// encode the public key in SEC1 format
+    pub fn to_sec1_bytes(&self, compressed: bool) -> Vec<u8> {
+        self.point.to_bytes(compressed)
+    }
+
+    /// return the public point
+    pub fn as_point(&self) -> &CurvePoint<C> {
+        &self.point
+    }
+
+    /// verify a signature of the digest of a message, both s and n - s are accepted
+    pub fn verify_prehash(&self, digest: &[u8], sig: &Signature<C>) -> bool {
+        self.verify_prehash_with(digest, sig, EcdsaVerification::Standard)
+    }
+
+    /// verify a signature of the digest of a message with the given check of s
+    pub fn verify_prehash_with(
+        &self,
+        digest: &[u8],
+        sig: &Signature<C>,
+        mode: EcdsaVerification,
+    ) -> bool {
+        if mode == EcdsaVerification::LowS && !sig.is_low_s() {
+            return false;
+        }
+        let z = bits2int(digest, C::order().bits());
+        verify_with_key(&Curve::<C>::new(), &self.point, &z, &sig.r, &sig.s)
+    }
+
+    /// verify a signature of msg hashed with D
+    pub fn verify_digest<D: Digest>(&self, msg: &[u8], sig: &Signature<C>) -> bool {
+        self.verify_prehash(&D::digest(msg), sig)
+    }
+
+    /// verify a signature of the SHA-256 hash of msg, reject a high s
+    pub fn verify_strict(&self, msg: &[u8], sig: &Signature<C>) -> bool {
+        self.verify_prehash_with(&Sha256::digest(msg), sig, EcdsaVerification::LowS)
+    }
+}
+
+impl<C: CurveParams> Verifier<Signature<C>> for VerifyingKey<C> {
+    /// verify a signature of the SHA-256 hash of msg
+    fn verify(&self, msg: &[u8], signature: &Signature<C>) -> bool {
+        self.verify_prehash(&Sha256::digest(msg), signature)
+    }
+}
+
+/// Recovery id of an ECDSA signature, which selects R among the points of x coordinate r
+///
+/// bit 0 is the parity of the y of R and bit 1 is set if the x of R is r + n
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecoveryId(u8);
+
+impl RecoveryId {
+    /// create a recovery id from the parity of y and the overflow of x
+    pub fn new(is_y_odd: bool, is_x_reduced: bool) -> Self {
+        Self(is_y_odd as u8 | (is_x_reduced as u8) << 1)
+    }
+
+    /// create a recovery id from a byte, return None if it is not in [0, 3]
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        (byte < 4).then_some(Self(byte))
+    }
+
+    /// return the recovery id as a byte in [0, 3]
+    pub fn to_byte(self) -> u8 {
+        self.0
+    }
+
+    /// return true if the y of R is odd
+    pub fn is_y_odd(self) -> bool {
+        self.0 & 1 == 1
+    }
+
+    /// return true if the x of R was reduced modulo n to get r
+    pub fn is_x_reduced(self) -> bool {
+        self.0 & 2 == 2
+    }
+}
+
+/// recover the public key of a signature of the SHA-256 hash of msg
+pub fn recover_verifying_key<C: CurveParams>(
+    msg: &[u8],
+    sig: &Signature<C>,
+    recid: RecoveryId,
+) -> Option<VerifyingKey<C>> {
+    recover_verifying_key_from_prehash(&Sha256::digest(msg), sig, recid)
+}
+
+/// recover the public key of a signature of a digest
+///
+/// R is lifted from r and the recovery id, then Q = r^-1 * (s*R - z*G).
+/// return None if there is no such R or Q is not a valid public key
+pub fn recover_verifying_key_from_prehash<C: CurveParams>(
+    digest: &[u8],
+    sig: &Signature<C>,
+    recid: RecoveryId,
+) -> Option<VerifyingKey<C>> {
+    let e = Curve::<C>::new();
+    let n = e.n();
+    let big_r = lift_r(&e, sig, recid)?;
+    let z = bits2int(digest, n.bits());
+    let r_inv = sig.r.modpow(&(n - BigUint::from(2_u32)), n);
+    let u1 = (n - z % n) * &r_inv % n;
+    let u2 = &sig.s * &r_inv % n;
+    VerifyingKey::from_point(e.g().shamir_mul(&u1, &big_r, &u2))
+}
+
+/// Signature of a digest to verify in a batch, with its public key and recovery id
+#[derive(Debug, Clone)]
+pub struct BatchItem<'a, C: CurveParams = Secp256k1Params> {
+    pub key: &'a VerifyingKey<C>,
+    pub digest: &'a [u8],
+    pub sig: &'a Signature<C>,
+    pub recid: RecoveryId,
+}
 
+/// verify many recoverable signatures at once
+///
+/// the recovery id gives the point R of every signature so each one is the equation
+/// R = u1*G + u2*Q. With random 128 bits a_i, the sum of a_i*(u1*G + u2*Q - R) must be
+/// infinity, which is a single multi scalar multiplication of 2m + 1 points instead of
+/// m Shamir multiplications. A forged signature passes with probability about 2^-128.
+///
+/// return Ok if all the signatures are valid, otherwise every item is verified alone and
+/// Err gives the indices of the invalid ones. An item is only valid if its recovery id is
+/// the right one.
+pub fn verify_batch<C: CurveParams>(items: &[BatchItem<C>]) -> Result<(), Vec<usize>> {
+    if batch_equation(items) {
+        return Ok(());
+    }
+    let invalid: Vec<usize> = (0..items.len())
+        .filter(|&i| !batch_equation(&items[i..i + 1]))
+        .collect();
+    // the random combination of valid items always holds, so invalid is not empty
+    Err(invalid)
+}
+
+/// return true if the random combination of the items is infinity
+fn batch_equation<C: CurveParams>(items: &[BatchItem<C>]) -> bool {
+    let e = Curve::<C>::new();
+    let n = e.n();
+    let mut rng = rand::thread_rng();
+    let mut points = vec![e.g().clone()];
+    let mut scalars = vec![BigUint::zero()];
+    for item in items {
+        let big_r = match lift_r(&e, item.sig, item.recid) {
+            Some(big_r) => big_r,
+            None => return false,
+        };
+        let z = bits2int(item.digest, n.bits());
+        let s_inv = item.sig.s.modpow(&(n - BigUint::from(2_u32)), n);
+        // a single item does not need a random factor
+        let a = match items.len() {
+            1 => BigUint::one(),
+            _ => rng.gen_biguint(128),
+        };
+        scalars[0] = (&scalars[0] + &a * z * &s_inv) % n;
+        points.push(item.key.point.clone());
+        scalars.push(&a * &item.sig.r * &s_inv % n);
+        points.push(big_r);
+        scalars.push((n - a % n) % n);
+    }
+    CurvePoint::msm(&points, &scalars).unwrap().is_infinity()
+}
+
+/// the point R of x coordinate r or r + n and y of the parity given by the recovery id
+fn lift_r<C: CurveParams>(
+    e: &Curve<C>,
+    sig: &Signature<C>,
+    recid: RecoveryId,
+) -> Option<CurvePoint<C>> {
+    let x = if recid.is_x_reduced() {
+        &sig.r + e.n()
+    } else {
+        sig.r.clone()
+    };
+    if x >= e.p {
+        return None;
+    }
+    let x = C::field(x);
+    let point = ECCPoint::lift_x(&x, recid.is_y_odd(), &e.a, &e.b)?;
+    Some(CurvePoint::from_ecc(point))
+}
+
+/// sign z with the nonce k, None if r = 0 or s = 0
+///
+/// s is normalized to the low s. k*G uses the constant time ladder, verify keeps the
+/// faster variable time path
+fn sign_with_k<C: CurveParams>(
+    e: &Curve<C>,
+    d: &BigUint,
+    z: &BigUint,
+    k: &BigUint,
+) -> Option<(Signature<C>, RecoveryId)> {
+    let n = e.n();
+    let (x, y) = e.g().constant_time_mul(k).xy();
+    let r = &x.num % n;
+    let k_inv = k.modpow(&(n - BigUint::from(2_u32)), n);
+    let s = (z + &r * d) * k_inv % n;
+    let sig = Signature::new(r, s)?;
+    if sig.is_low_s() {
+        return Some((sig, RecoveryId::new(y.num.bit(0), x.num >= *n)));
+    }
+    // n - s is the signature of -k, so R becomes -R and the parity of y flips
+    Some((
+        sig.normalize_s(),
+        RecoveryId::new(!y.num.bit(0), x.num >= *n),
+    ))
+}
+
+/// sign the digest with the RFC 6979 nonces, the next nonce is used if r = 0 or s = 0
+fn sign_deterministic<C: CurveParams, D: Digest + BlockSizeUser>(
+    e: &Curve<C>,
+    d: &BigUint,
+    digest: &[u8],
+    extra: &[u8],
+) -> (Signature<C>, RecoveryId) {
+    let z = bits2int(digest, e.n().bits());
+    Rfc6979::<D>::with_digest(d, e.n(), digest, extra)
+        .find_map(|k| sign_with_k(e, d, &z, &k))
+        .unwrap()
+}
+
+fn verify_with_key<C: CurveParams>(
+    e: &Curve<C>,
+    q: &CurvePoint<C>,
+    z: &BigUint,
+    r: &BigUint,
+    s: &BigUint,
+) -> bool {
+    let n = e.n();
+    if r.is_zero() || s.is_zero() || r >= n || s >= n {
+        return false;
+    }
+    let s_inv = s.modpow(&(n - BigUint::from(2_u32)), n);
+    let u1 = z * &s_inv % n;
+    let u2 = r * &s_inv % n;
+    let p = e.g().shamir_mul(&u1, q, &u2);
+    r == &(p.x().num % n)
+}
+
+#[cfg(test)]
 mod tests {
     use super::*;
+    use crate::curve::from_hex;
+    use crate::p256::P256Params;
+    use crate::p384::P384Params;
+    use crate::p521::P521Params;
+    use crate::test_utils::hex;
+    use num_traits::One;
+    use sha2::Sha384;
+    use sha3::{Keccak256, Sha3_256};
     use std::str::FromStr;
 
     #[test]
     fn test_hash() {
         let mut rng = rand::thread_rng();
-        let d: BigUint = rng.sample(RandomBits::new(256));
-        let ecdsa = Ecdsa::new(d);
+        let d = rng.gen_biguint_range(&BigUint::one(), &Secp256k1Params::order());
+        let ecdsa: Ecdsa = Ecdsa::new(d).unwrap();
         let m = "hello";
         let h = ecdsa.hash(m);
         assert_eq!(
@@ -74,10 +704,511 @@ mod tests {
     #[test]
     fn test_sign_verify() {
         let mut rng = rand::thread_rng();
-        let d: BigUint = rng.sample(RandomBits::new(256));
-        let ecdsa = Ecdsa::new(d);
+        let d = rng.gen_biguint_range(&BigUint::one(), &Secp256k1Params::order());
+        let ecdsa: Ecdsa = Ecdsa::new(d).unwrap();
         let m = "no.more.caffeine";
-        let (r, s) = ecdsa.sign(m);
-        assert!(ecdsa.verify(m, &r, &s));
+        let sig = ecdsa.sign(m);
+        assert!(ecdsa.verify(m, &sig));
+    }
+
+    #[test]
+    fn test_signing_key() {
+        let mut rng = rand::thread_rng();
+        let signing_key = SigningKey::<Secp256k1Params>::random(&mut rng);
+        let verifying_key = signing_key.verifying_key().clone();
+        let sig = signing_key.sign(b"no.more.caffeine");
+        assert!(verifying_key.verify(b"no.more.caffeine", &sig));
+        assert!(!verifying_key.verify(b"no.more.tea", &sig));
+
+        // the verifier only needs the SEC1 encoding of the public key
+        let bytes = verifying_key.to_sec1_bytes(true);
+        let decoded = VerifyingKey::<Secp256k1Params>::from_sec1_bytes(&bytes).unwrap();
+        assert_eq!(decoded, verifying_key);
+        assert!(decoded.verify(b"no.more.caffeine", &sig));
+
+        // same signature as the Ecdsa with the same private key
+        let ecdsa: Ecdsa = Ecdsa::new(BigUint::from_bytes_be(&signing_key.to_bytes())).unwrap();
+        assert_eq!(ecdsa.verifying_key(), verifying_key);
+        assert_eq!(ecdsa.sign("no.more.caffeine"), sig);
+    }
+
+    #[test]
+    fn test_low_s() {
+        let n = Secp256k1Params::order();
+        let signing_key = SigningKey::<Secp256k1Params>::from_bytes(&[42]).unwrap();
+        let verifying_key = signing_key.verifying_key();
+        for m in ["a", "b", "c", "d"] {
+            let sig = signing_key.sign(m.as_bytes());
+            assert!(sig.is_low_s());
+            let high = Signature::new(sig.r().clone(), &n - sig.s()).unwrap();
+            assert!(!high.is_low_s());
+            assert_eq!(high.normalize_s(), sig);
+            // the malleated signature is only rejected by the strict check
+            assert!(verifying_key.verify(m.as_bytes(), &high));
+            assert!(!verifying_key.verify_strict(m.as_bytes(), &high));
+            assert!(verifying_key.verify_strict(m.as_bytes(), &sig));
+        }
+
+        let ecdsa = Ecdsa::<Secp256k1Params>::new(BigUint::from(42_u32)).unwrap();
+        for m in ["a", "b", "c", "d"] {
+            let sig = ecdsa.sign(m);
+            assert!(sig.is_low_s());
+            assert!(ecdsa.sign_with_entropy(m, b"nonce").is_low_s());
+            assert!(ecdsa.sign_digest::<Sha384>(m.as_bytes()).is_low_s());
+            let high = Signature::new(sig.r().clone(), &n - sig.s()).unwrap();
+            assert!(ecdsa.verify(m, &high));
+            assert!(!ecdsa.verify_strict(m, &high));
+            assert!(ecdsa.verify_strict(m, &sig));
+        }
+    }
+
+    #[test]
+    fn test_recover() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..5 {
+            let signing_key = SigningKey::<Secp256k1Params>::random(&mut rng);
+            let (sig, recid) = signing_key.sign_recoverable(b"no.more.caffeine");
+            assert!(sig.is_low_s());
+            let recovered = recover_verifying_key(b"no.more.caffeine", &sig, recid);
+            assert_eq!(recovered.as_ref(), Some(signing_key.verifying_key()));
+            // the other parity gives another key
+            let other = RecoveryId::new(!recid.is_y_odd(), false);
+            let recovered = recover_verifying_key(b"no.more.caffeine", &sig, other);
+            assert_ne!(recovered.as_ref(), Some(signing_key.verifying_key()));
+        }
+        assert_eq!(RecoveryId::from_byte(4), None);
+        assert_eq!(RecoveryId::from_byte(3), Some(RecoveryId::new(true, true)));
+    }
+
+    #[test]
+    fn test_recover_ethereum() {
+        // EIP-155 example transaction signed with the key 0x4646...46, v = 37 so recid = 0
+        // https://eips.ethereum.org/EIPS/eip-155
+        let signing_key = SigningKey::<Secp256k1Params>::from_bytes(&[0x46; 32]).unwrap();
+        let digest = hex("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53");
+        let sig = Signature::new(
+            BigUint::from_str(
+                "18515461264373351373200002665853028612451056578545711640558177340181847433846",
+            )
+            .unwrap(),
+            BigUint::from_str(
+                "46948507304638947509940763649030358759909902576025900602547168820602576006531",
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        let recid = RecoveryId::from_byte(37 - 35 - 2).unwrap();
+        let recovered = recover_verifying_key_from_prehash(&digest, &sig, recid).unwrap();
+        assert_eq!(&recovered, signing_key.verifying_key());
+        assert!(recovered.verify_prehash(&digest, &sig));
+        assert_eq!(signing_key.sign_prehash_recoverable(&digest), (sig, recid));
+    }
+
+    #[test]
+    fn test_der() {
+        // RFC 6979 P-256 signature of "sample", both integers need a leading zero
+        let sig = Signature::<P256Params>::new(
+            from_hex("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"),
+            from_hex("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"),
+        )
+        .unwrap();
+        let der = hex(
+            "3046022100efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716\
+             022100f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8",
+        );
+        assert_eq!(sig.to_der(), der);
+        assert_eq!(Signature::from_der(&der), Some(sig));
+
+        let one = Signature::<Secp256k1Params>::new(BigUint::one(), BigUint::one()).unwrap();
+        assert_eq!(one.to_der(), hex("3006020101020101"));
+        assert_eq!(Signature::from_der(&hex("3006020101020101")), Some(one));
+
+        // P-521 integers need the long form of the sequence length
+        let n = P521Params::order();
+        let sig = Signature::<P521Params>::new(&n - 1_u32, &n - 2_u32).unwrap();
+        let der = sig.to_der();
+        assert_eq!(&der[..3], &[0x30, 0x81, 0x88]);
+        assert_eq!(Signature::from_der(&der), Some(sig));
+    }
+
+    #[test]
+    fn test_der_strict() {
+        let invalid = [
+            "",
+            "3106020101020101",
+            // trailing data, after the sequence and inside it
+            "300602010102010100",
+            "3009020101020101020101",
+            // wrong lengths
+            "3007020101020101",
+            "3005020101020101",
+            "3006020201020101",
+            // long form of a short length
+            "308106020101020101",
+            "300702010102810101",
+            // negative, non minimal and empty integers
+            "3006020180020101",
+            "300702020001020101",
+            "30050200020101",
+            // r = 0 is out of range
+            "3006020100020101",
+        ];
+        for der in invalid {
+            assert_eq!(Signature::<Secp256k1Params>::from_der(&hex(der)), None);
+        }
+        // s = n is out of range
+        let sig = Signature::<Secp256k1Params> {
+            r: BigUint::one(),
+            s: Secp256k1Params::order(),
+            _curve: PhantomData,
+        };
+        assert_eq!(Signature::<Secp256k1Params>::from_der(&sig.to_der()), None);
+        // a leading zero is needed when the high bit is set
+        let der = hex("30080202008002020080");
+        assert!(Signature::<Secp256k1Params>::from_der(&der).is_some());
+    }
+
+    #[test]
+    fn test_compact() {
+        let signing_key = SigningKey::<Secp256k1Params>::from_bytes(&[0x46; 32]).unwrap();
+        let (sig, recid) = signing_key.sign_recoverable(b"no.more.caffeine");
+        let compact = sig.to_compact();
+        assert_eq!(compact.len(), 64);
+        assert_eq!(Signature::from_compact(&compact), Some(sig.clone()));
+        assert_eq!(
+            Signature::<Secp256k1Params>::from_compact(&compact[1..]),
+            None
+        );
+
+        let bytes = sig.to_recoverable_bytes(recid);
+        assert_eq!(bytes.len(), 65);
+        assert_eq!(
+            Signature::from_recoverable_bytes(&bytes),
+            Some((sig, recid))
+        );
+        let mut bad = bytes.clone();
+        bad[64] = 4;
+        assert_eq!(
+            Signature::<Secp256k1Params>::from_recoverable_bytes(&bad),
+            None
+        );
+        assert_eq!(
+            Signature::<P521Params>::new(BigUint::one(), BigUint::one())
+                .unwrap()
+                .to_compact()
+                .len(),
+            132
+        );
+    }
+
+    #[test]
+    fn test_digest() {
+        // RFC 6979 appendix A.2.5, P-256 with SHA-384, the digest is truncated to 256 bits
+        let ecdsa = Ecdsa::<P256Params>::new(from_hex(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ))
+        .unwrap();
+        let sig = ecdsa.sign_digest::<Sha384>(b"sample");
+        assert_eq!(
+            sig,
+            signature(
+                "0eafea039b20e9b42309fb1d89e213057cbf973dc0cfc8f129edddc800ef7719",
+                "4861f0491e6998b9455193e34e7b0d284ddd7149a74b95b9261f13abde940954"
+            )
+        );
+        assert!(ecdsa.verify_digest::<Sha384>(b"sample", &sig));
+        assert!(ecdsa.verify_prehash(&Sha384::digest(b"sample"), &sig));
+        assert!(!ecdsa.verify_digest::<Sha256>(b"sample", &sig));
+        assert_eq!(ecdsa.sign_digest::<Sha256>(b"sample"), ecdsa.sign("sample"));
+        assert_eq!(
+            ecdsa.sign_prehash(&Sha256::digest(b"sample")),
+            ecdsa.sign("sample")
+        );
+
+        let signing_key = SigningKey::<Secp256k1Params>::from_bytes(&[7; 32]).unwrap();
+        let sig = signing_key.sign_digest::<Sha3_256>(b"no.more.caffeine");
+        let verifying_key = signing_key.verifying_key();
+        assert!(verifying_key.verify_digest::<Sha3_256>(b"no.more.caffeine", &sig));
+        assert!(!verifying_key.verify_digest::<Keccak256>(b"no.more.caffeine", &sig));
+    }
+
+    #[test]
+    fn test_keccak_ethereum() {
+        // EIP-155 example: Keccak-256 of the RLP of the transaction gives the hash signed
+        // in test_recover_ethereum
+        let signing_key = SigningKey::<Secp256k1Params>::from_bytes(&[0x46; 32]).unwrap();
+        let rlp = hex(
+            "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400\
+             0080018080",
+        );
+        assert_eq!(
+            Keccak256::digest(&rlp).to_vec(),
+            hex("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53")
+        );
+        // Ethereum signs the Keccak-256 digest with HMAC-SHA-256 nonces
+        let digest = Keccak256::digest(&rlp);
+        let (sig, recid) = signing_key.sign_prehash_recoverable(&digest);
+        assert_eq!(recid.to_byte(), 0);
+        assert_eq!(
+            sig.r(),
+            &BigUint::from_str(
+                "18515461264373351373200002665853028612451056578545711640558177340181847433846"
+            )
+            .unwrap()
+        );
+        // HMAC-Keccak-256 nonces give another valid signature
+        let (sig, recid) = signing_key.sign_digest_recoverable::<Keccak256>(&rlp);
+        let recovered = recover_verifying_key_from_prehash(&digest, &sig, recid);
+        assert_eq!(recovered.as_ref(), Some(signing_key.verifying_key()));
+
+        // the address is the last 20 bytes of the Keccak-256 of x || y
+        let public = signing_key.verifying_key().to_sec1_bytes(false);
+        let address = Keccak256::digest(&public[1..]);
+        assert_eq!(
+            address[12..].to_vec(),
+            hex("9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f")
+        );
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let mut rng = rand::thread_rng();
+        let keys: Vec<SigningKey> = (0..4).map(|_| SigningKey::random(&mut rng)).collect();
+        let digests: Vec<Vec<u8>> = (0..20_u32)
+            .map(|i| Sha256::digest(i.to_be_bytes()).to_vec())
+            .collect();
+        let sigs: Vec<(Signature, RecoveryId)> = digests
+            .iter()
+            .enumerate()
+            .map(|(i, digest)| keys[i % 4].sign_prehash_recoverable(digest))
+            .collect();
+        fn items<'a>(
+            keys: &'a [SigningKey],
+            digests: &'a [Vec<u8>],
+            sigs: &'a [(Signature, RecoveryId)],
+        ) -> Vec<BatchItem<'a>> {
+            sigs.iter()
+                .enumerate()
+                .map(|(i, (sig, recid))| BatchItem {
+                    key: keys[i % 4].verifying_key(),
+                    digest: &digests[i],
+                    sig,
+                    recid: *recid,
+                })
+                .collect()
+        }
+        assert_eq!(verify_batch(&items(&keys, &digests, &sigs)), Ok(()));
+        assert_eq!(verify_batch::<Secp256k1Params>(&[]), Ok(()));
+
+        // a signature of another digest, a wrong recovery id and n - s with the recovery id of s
+        let mut bad = sigs.clone();
+        bad[3] = sigs[4].clone();
+        bad[11].1 = RecoveryId::new(!sigs[11].1.is_y_odd(), false);
+        let n = Secp256k1Params::order();
+        bad[17].0 = Signature::new(sigs[17].0.r().clone(), &n - sigs[17].0.s()).unwrap();
+        assert_eq!(
+            verify_batch(&items(&keys, &digests, &bad)),
+            Err(vec![3, 11, 17])
+        );
+    }
+
+    #[test]
+    fn test_verify_range() {
+        let ecdsa: Ecdsa = Ecdsa::new(BigUint::from(12345_u32)).unwrap();
+        let n = ecdsa.e.n().clone();
+        let z = ecdsa.hash("no.more.caffeine");
+        let sig = ecdsa.sign("no.more.caffeine");
+        assert!(ecdsa.verify_hash(&z, &sig));
+        let (r, s) = (sig.r(), sig.s());
+        let verify = |r: &BigUint, s: &BigUint| verify_with_key(&ecdsa.e, &ecdsa.pub_key, &z, r, s);
+        assert!(verify(r, s));
+        assert!(!verify(r, &BigUint::zero()));
+        assert!(!verify(&BigUint::zero(), s));
+        assert!(!verify(&(r + &n), s));
+        assert!(!verify(r, &(s + &n)));
+    }
+
+    #[test]
+    fn test_hash_truncation() {
+        // a 160 bits order keeps the leftmost 160 bits of the digest
+        #[derive(Debug, Clone, PartialEq)]
+        struct Small;
+        impl CurveParams for Small {
+            const NAME: &'static str = "small";
+            const P: &'static str = Secp256k1Params::P;
+            const A: &'static str = Secp256k1Params::A;
+            const B: &'static str = Secp256k1Params::B;
+            const GX: &'static str = Secp256k1Params::GX;
+            const GY: &'static str = Secp256k1Params::GY;
+            const N: &'static str = "ffffffffffffffffffffffffffffffffffffffff";
+            const H: &'static str = "1";
+        }
+        let ecdsa = Ecdsa::<Small>::new(BigUint::one()).unwrap();
+        let digest = Sha256::digest(b"hello");
+        assert_eq!(ecdsa.hash("hello"), BigUint::from_bytes_be(&digest[..20]));
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let n = Secp256k1Params::order();
+        assert!(SigningKey::<Secp256k1Params>::from_bytes(&[0]).is_none());
+        assert!(SigningKey::<Secp256k1Params>::from_bytes(&n.to_bytes_be()).is_none());
+        assert!(Signature::<Secp256k1Params>::new(BigUint::zero(), BigUint::one()).is_none());
+        assert!(Signature::<Secp256k1Params>::new(BigUint::one(), n).is_none());
+        let infinity = Curve::<Secp256k1Params>::new().infinity();
+        assert!(VerifyingKey::from_point(infinity).is_none());
+    }
+
+    #[test]
+    fn test_private_key_range() {
+        let n = Secp256k1Params::order();
+        assert!(Ecdsa::<Secp256k1Params>::new(BigUint::zero()).is_none());
+        assert!(Ecdsa::<Secp256k1Params>::new(n.clone()).is_none());
+        assert!(Ecdsa::<Secp256k1Params>::new(n - 1_u32).is_some());
+    }
+
+    #[test]
+    fn test_rfc6979() {
+        // RFC 6979 appendix A.2.5, P-256 with SHA-256
+        let ecdsa = Ecdsa::<P256Params>::new(from_hex(
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721",
+        ))
+        .unwrap();
+        assert_eq!(
+            ecdsa.pub_key,
+            ecdsa.e.point(
+                from_hex("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6"),
+                from_hex("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299")
+            )
+        );
+        // the RFC lists the high s, the signing methods return n - s
+        assert_eq!(
+            ecdsa.sign("sample"),
+            signature(
+                "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716",
+                "f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"
+            )
+            .normalize_s()
+        );
+        assert_eq!(
+            ecdsa.sign("test"),
+            signature(
+                "f1abb023518351cd71d881567b1ea663ed3efcf6c5132b354f28d3b0b7d38367",
+                "019f4113742a2b14bd25926b49c649155f267e60d3814b4c0cc84250e46f0083"
+            )
+        );
+
+        let sig = ecdsa.sign_with_entropy("sample", b"nonce");
+        assert_ne!(sig, ecdsa.sign("sample"));
+        assert!(ecdsa.verify("sample", &sig));
+    }
+
+    #[test]
+    fn test_sign_verify_nist() {
+        let m = "no.more.caffeine";
+        let ecdsa = Ecdsa::<P256Params>::new(BigUint::from(12345_u32)).unwrap();
+        let sig = ecdsa.sign(m);
+        assert!(ecdsa.verify(m, &sig));
+        assert!(!ecdsa.verify("no.more.tea", &sig));
+
+        let ecdsa = Ecdsa::<P384Params>::new(BigUint::from(12345_u32)).unwrap();
+        let sig = ecdsa.sign(m);
+        assert!(ecdsa.verify(m, &sig));
+
+        let ecdsa = Ecdsa::<P521Params>::new(BigUint::from(12345_u32)).unwrap();
+        let sig = ecdsa.sign(m);
+        assert!(ecdsa.verify(m, &sig));
+    }
+
+    /// FIPS 186-4 SigGen test vectors: (d, qx, qy, k, hash of message, r, s)
+    /// https://csrc.nist.gov/projects/cryptographic-algorithm-validation-program/digital-signatures
+    fn check_cavp<C: CurveParams>(vectors: &[[&str; 7]]) {
+        for [d, qx, qy, k, z, r, s] in vectors {
+            let ecdsa = Ecdsa::<C>::new(from_hex(d)).unwrap();
+            assert_eq!(ecdsa.pub_key, ecdsa.e.point(from_hex(qx), from_hex(qy)));
+            let sig = signature(r, s).normalize_s();
+            assert_eq!(
+                ecdsa.sign_hash(&from_hex(z), &from_hex(k)),
+                Some(sig.clone())
+            );
+            assert!(ecdsa.verify_hash(&from_hex(z), &sig));
+            assert!(!ecdsa.verify_hash(&(from_hex(z) + BigUint::one()), &sig));
+        }
+    }
+
+    fn signature<C: CurveParams>(r: &str, s: &str) -> Signature<C> {
+        Signature::new(from_hex(r), from_hex(s)).unwrap()
+    }
+
+    #[test]
+    fn test_cavp_p256() {
+        check_cavp::<P256Params>(&[
+            [
+                "519b423d715f8b581f4fa8ee59f4771a5b44c8130b4e3eacca54a56dda72b464",
+                "1ccbe91c075fc7f4f033bfa248db8fccd3565de94bbfb12f3c59ff46c271bf83",
+                "ce4014c68811f9a21a1fdb2c0e6113e06db7ca93b7404e78dc7ccd5ca89a4ca9",
+                "94a1bbb14b906a61a280f245f9e93c7f3b4a6247824f5d33b9670787642a68de",
+                "44acf6b7e36c1342c2c5897204fe09504e1e2efb1a900377dbc4e7a6a133ec56",
+                "f3ac8061b514795b8843e3d6629527ed2afd6b1f6a555a7acabb5e6f79c8c2ac",
+                "8bf77819ca05a6b2786c76262bf7371cef97b218e96f175a3ccdda2acc058903",
+            ],
+            [
+                "0f56db78ca460b055c500064824bed999a25aaf48ebb519ac201537b85479813",
+                "e266ddfdc12668db30d4ca3e8f7749432c416044f2d2b8c10bf3d4012aeffa8a",
+                "bfa86404a2e9ffe67d47c587ef7a97a7f456b863b4d02cfc6928973ab5b1cb39",
+                "6d3e71882c3b83b156bb14e0ab184aa9fb728068d3ae9fac421187ae0b2f34c6",
+                "9b2db89cb0e8fa3cc7608b4d6cc1dec0114e0b9ff4080bea12b134f489ab2bbc",
+                "976d3a4e9d23326dc0baa9fa560b7c4e53f42864f508483a6473b6a11079b2db",
+                "1b766e9ceb71ba6c01dcd46e0af462cd4cfa652ae5017d4555b8eeefe36e1932",
+            ],
+        ]);
+    }
+
+    #[test]
+    fn test_cavp_p384() {
+        check_cavp::<P384Params>(&[
+            [
+                "201b432d8df14324182d6261db3e4b3f46a8284482d52e370da41e6cbdf45ec2952f5db7ccbce3bc29449f4fb080ac97",
+                "c2b47944fb5de342d03285880177ca5f7d0f2fcad7678cce4229d6e1932fcac11bfc3c3e97d942a3c56bf34123013dbf",
+                "37257906a8223866eda0743c519616a76a758ae58aee81c5fd35fbf3a855b7754a36d4a0672df95d6c44a81cf7620c2d",
+                "dcedabf85978e090f733c6e16646fa34df9ded6e5ce28c6676a00f58a25283db8885e16ce5bf97f917c81e1f25c9c771",
+                "31a452d6164d904bb5724c878280231eae705c29ce9d4bc7d58e020e1085f17eebcc1a38f0ed0bf2b344d81fbd896825",
+                "50835a9251bad008106177ef004b091a1e4235cd0da84fff54542b0ed755c1d6f251609d14ecf18f9e1ddfe69b946e32",
+                "475f3d30c6463b646e8d3bf2455830314611cbde404be518b14464fdb195fdcc92eb222e61f426a4a592c00a6a89721",
+            ],
+            [
+                "23d9f4ea6d87b7d6163d64256e3449255db14786401a51daa7847161bf56d494325ad2ac8ba928394e01061d882c3528",
+                "5d42d6301c54a438f65970bae2a098cbc567e98840006e356221966c86d82e8eca515bca850eaa3cd41f175f03a0cbfd",
+                "4aef5a0ceece95d382bd70ab5ce1cb77408bae42b51a08816d5e5e1d3da8c18fcc95564a752730b0aabea983ccea4e2e",
+                "67ba379366049008593eac124f59ab017358892ee0c063d38f3758bb849fd25d867c3561563cac1532a323b228dc0890",
+                "a92784916a40feaebfeab16ea28c0c65e45c5e81eb634052944865708072e20110bd669a9838d7e722e94ac75245cdd3",
+                "fb318f4cb1276282bb43f733a7fb7c567ce94f4d02924fc758635ab2d1107108bf159b85db080cdc3b30fbb5400016f3",
+                "588e3d7af5da03eae255ecb1813100d95edc243476b724b22db8e85377660d7645ddc1c2c2ee4eaea8b683dbe22f86ca",
+            ],
+        ]);
+    }
+
+    #[test]
+    fn test_cavp_p521() {
+        check_cavp::<P521Params>(&[
+            [
+                "f749d32704bc533ca82cef0acf103d8f4fba67f08d2678e515ed7db886267ffaf02fab0080dca2359b72f574ccc29a0f218c8655c0cccf9fee6c5e567aa14cb926",
+                "61387fd6b95914e885f912edfbb5fb274655027f216c4091ca83e19336740fd81aedfe047f51b42bdf68161121013e0d55b117a14e4303f926c8debb77a7fdaad1",
+                "e7d0c75c38626e895ca21526b9f9fdf84dcecb93f2b233390550d2b1463b7ee3f58df7346435ff0434199583c97c665a97f12f706f2357da4b40288def888e59e6",
+                "3af5ab6caa29a6de86a5bab9aa83c3b16a17ffcd52b5c60c769be3053cdddeac60812d12fecf46cfe1f3db9ac9dcf881fcec3f0aa733d4ecbb83c7593e864c6df1",
+                "65f83408092261bda599389df03382c5be01a81fe00a36f3f4bb6541263f801627c440e50809712b0cace7c217e6e5051af81de9bfec3204dcd63c4f9a741047",
+                "4de826ea704ad10bc0f7538af8a3843f284f55c8b946af9235af5af74f2b76e099e4bc72fd79d28a380f8d4b4c919ac290d248c37983ba05aea42e2dd79fdd33e8",
+                "87488c859a96fea266ea13bf6d114c429b163be97a57559086edb64aed4a18594b46fb9efc7fd25d8b2de8f09ca0587f54bd287299f47b2ff124aac566e8ee3b43",
+            ],
+            [
+                "1a4d2623a7d59c55f408331ba8d1523b94d6bf8ac83375ceb57a2b395a5bcf977cfc16234d4a97d6f6ee25a99aa5bff15ff535891bcb7ae849a583e01ac49e0e9b6",
+                "4d5c8afee038984d2ea96681ec0dccb6b52dfa4ee2e2a77a23c8cf43ef19905a34d6f5d8c5cf0981ed804d89d175b17d1a63522ceb1e785c0f5a1d2f3d15e51352",
+                "14368b8e746807b2b68f3615cd78d761a464ddd7918fc8df51d225962fdf1e3dc243e265100ff0ec133359e332e44dd49afd8e5f38fe86133573432d33c02fa0a3",
+                "bc2c0f37155859303de6fa539a39714e195c37c6ea826e224c8218584ae09cd0d1cc14d94d93f2d83c96e4ef68517fdb3f383da5404e5a426bfc5d424e253c181b",
+                "a6200971c6a289e2fcb80f78ec08a5079ea2675efd68bcab479552aa5bcb8edf3c993c79d7cebcc23c20e5af41723052b871134cc71d5c57206182a7068cc39b",
+                "1a3c4a6386c4fb614fba2cb9e74201e1aaa0001aa931a2a939c92e04b8344535a20f53c6e3c69c75c2e5d2fe3549ed27e6713cb0f4a9a94f6189eb33bff7d453fce",
+                "16a997f81aa0bea2e1469c8c1dab7df02a8b2086ba482c43af04f2174831f2b1761658795adfbdd44190a9b06fe10e578987369f3a2eced147cff89d8c2818f7471",
+            ],
+        ]);
     }
 }
@@ -0,0 +1,181 @@
+use crate::curve::CurveParams;
+use crate::ecdh::diffie_hellman;
+use crate::ecdsa::{SigningKey, VerifyingKey};
+use aead::generic_array::typenum::Unsigned;
+use aead::{Aead, AeadCore, KeyInit, Nonce, Payload};
+use rand::{CryptoRng, RngCore};
+
+/// info of the HKDF which derives the AEAD key and nonce
+const ECIES_INFO: &[u8] = b"ECIES HKDF-SHA-256";
+
+/// Encrypt plaintext to the public key with ECIES and the AEAD A, e.g. Aes256Gcm or
+/// ChaCha20Poly1305
+///
+/// a random ephemeral key R = r*G gives the shared secret x of r*Q, HKDF-SHA-256 with
+/// R as salt derives the AEAD key followed by the nonce, which is safe as every key is
+/// used once. The ciphertext is R in compressed SEC1 || AEAD ciphertext || tag, the aad
+/// is authenticated but not encrypted.
+pub fn ecies_encrypt<C, A, R>(
+    rng: &mut R,
+    public: &VerifyingKey<C>,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Vec<u8>
+where
+    C: CurveParams,
+    A: Aead + KeyInit,
+    R: RngCore + CryptoRng,
+{
+    encrypt_with_ephemeral::<C, A>(&SigningKey::random(rng), public, plaintext, aad)
+}
+
+/// Decrypt an ECIES ciphertext of ecies_encrypt, return None if it is malformed or the
+/// tag does not match
+pub fn ecies_decrypt<C, A>(secret: &SigningKey<C>, ciphertext: &[u8], aad: &[u8]) -> Option<Vec<u8>>
+where
+    C: CurveParams,
+    A: Aead + KeyInit,
+{
+    let len = 1 + C::p().bits().div_ceil(8) as usize;
+    if ciphertext.len() < len || !matches!(ciphertext[0], 0x02 | 0x03) {
+        return None;
+    }
+    let (encapsulated, ciphertext) = ciphertext.split_at(len);
+    let ephemeral = VerifyingKey::<C>::from_sec1_bytes(encapsulated)?;
+    let (cipher, nonce) = derive_cipher::<C, A>(secret, &ephemeral, encapsulated);
+    let payload = Payload {
+        msg: ciphertext,
+        aad,
+    };
+    cipher.decrypt(&nonce, payload).ok()
+}
+
+fn encrypt_with_ephemeral<C, A>(
+    ephemeral: &SigningKey<C>,
+    public: &VerifyingKey<C>,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Vec<u8>
+where
+    C: CurveParams,
+    A: Aead + KeyInit,
+{
+    let mut out = ephemeral.verifying_key().to_sec1_bytes(true);
+    let (cipher, nonce) = derive_cipher::<C, A>(ephemeral, public, &out);
+    let payload = Payload {
+        msg: plaintext,
+        aad,
+    };
+    let ciphertext = cipher
+        .encrypt(&nonce, payload)
+        .expect("plaintext is too long for the AEAD");
+    out.extend_from_slice(&ciphertext);
+    out
+}
+
+/// the AEAD and the nonce derived from the shared secret of secret and public
+fn derive_cipher<C, A>(
+    secret: &SigningKey<C>,
+    public: &VerifyingKey<C>,
+    encapsulated: &[u8],
+) -> (A, Nonce<A>)
+where
+    C: CurveParams,
+    A: Aead + KeyInit,
+{
+    let key_len = A::key_size();
+    let nonce_len = <A as AeadCore>::NonceSize::USIZE;
+    let okm =
+        diffie_hellman(secret, public).derive_key(encapsulated, ECIES_INFO, key_len + nonce_len);
+    let cipher = A::new_from_slice(&okm[..key_len]).unwrap();
+    (cipher, Nonce::<A>::clone_from_slice(&okm[key_len..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p256::P256Params;
+    use crate::secp256k1::Secp256k1Params;
+    use crate::test_utils::hex;
+    use aes_gcm::Aes256Gcm;
+    use chacha20poly1305::ChaCha20Poly1305;
+
+    fn roundtrip<C: CurveParams, A: Aead + KeyInit>() {
+        let mut rng = rand::thread_rng();
+        let key = SigningKey::<C>::random(&mut rng);
+        let msg = b"attack at dawn";
+        let ct = ecies_encrypt::<C, A, _>(&mut rng, key.verifying_key(), msg, b"header");
+        let len = 1 + C::p().bits().div_ceil(8) as usize;
+        assert_eq!(ct.len(), len + msg.len() + 16);
+        assert_eq!(
+            ecies_decrypt::<C, A>(&key, &ct, b"header").unwrap(),
+            msg.to_vec()
+        );
+        // a fresh ephemeral key every time
+        assert_ne!(
+            ecies_encrypt::<C, A, _>(&mut rng, key.verifying_key(), msg, b"header"),
+            ct
+        );
+        assert_eq!(
+            ecies_decrypt::<C, A>(
+                &key,
+                &ecies_encrypt::<C, A, _>(&mut rng, key.verifying_key(), &[], &[]),
+                &[]
+            ),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        roundtrip::<Secp256k1Params, Aes256Gcm>();
+        roundtrip::<Secp256k1Params, ChaCha20Poly1305>();
+        roundtrip::<P256Params, Aes256Gcm>();
+    }
+
+    #[test]
+    fn test_reject() {
+        let mut rng = rand::thread_rng();
+        let key = SigningKey::<Secp256k1Params>::random(&mut rng);
+        let other = SigningKey::<Secp256k1Params>::random(&mut rng);
+        let ct = ecies_encrypt::<_, Aes256Gcm, _>(&mut rng, key.verifying_key(), b"msg", b"aad");
+        let decrypt = |ct: &[u8], aad: &[u8]| ecies_decrypt::<_, Aes256Gcm>(&key, ct, aad);
+        assert!(decrypt(&ct, b"aad").is_some());
+        assert!(decrypt(&ct, b"other").is_none());
+        assert!(ecies_decrypt::<_, Aes256Gcm>(&other, &ct, b"aad").is_none());
+        assert!(ecies_decrypt::<_, ChaCha20Poly1305>(&key, &ct, b"aad").is_none());
+        for i in [0, 1, 33, ct.len() - 1] {
+            let mut tampered = ct.clone();
+            tampered[i] ^= 1;
+            assert!(decrypt(&tampered, b"aad").is_none());
+        }
+        assert!(decrypt(&ct[..ct.len() - 1], b"aad").is_none());
+        assert!(decrypt(&ct[..33], b"aad").is_none());
+        assert!(decrypt(&ct[..10], b"aad").is_none());
+        // the uncompressed ephemeral key is not part of the format
+        let mut uncompressed = key.verifying_key().to_sec1_bytes(false);
+        uncompressed.extend_from_slice(&ct[33..]);
+        assert!(decrypt(&uncompressed, b"aad").is_none());
+    }
+
+    #[test]
+    fn test_ciphertext_format() {
+        // pins the KDF and the layout so ciphertexts stay decryptable across versions
+        let key = SigningKey::<Secp256k1Params>::from_bytes(&[1]).unwrap();
+        let ephemeral = SigningKey::<Secp256k1Params>::from_bytes(&[2]).unwrap();
+        let ct =
+            encrypt_with_ephemeral::<_, Aes256Gcm>(&ephemeral, key.verifying_key(), b"hello", b"");
+        assert_eq!(ct[..33], ephemeral.verifying_key().to_sec1_bytes(true));
+        assert_eq!(
+            ct,
+            hex(
+                "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5\
+                 b119b71195e70e199045c0a7f85d275de1d0720765"
+            )
+        );
+        assert_eq!(
+            ecies_decrypt::<_, Aes256Gcm>(&key, &ct, b"").unwrap(),
+            b"hello".to_vec()
+        );
+    }
+}
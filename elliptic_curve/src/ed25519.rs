@@ -0,0 +1,370 @@
+use crate::edwards::{EdwardsParams, EdwardsPoint};
+use crate::signature::{Signer, Verifier};
+use num_bigint::BigUint;
+use rand::RngCore;
+use sha2::{Digest, Sha512};
+use zeroize::Zeroize;
+
+/// Parameters of edwards25519, the twisted edwards form of Curve25519
+/// -x^2 + y^2 = 1 + d*x^2*y^2 over F_p with p = 2^255 - 19
+/// https://www.rfc-editor.org/rfc/rfc8032#section-5.1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Ed25519Params;
+
+impl EdwardsParams for Ed25519Params {
+    const NAME: &'static str = "edwards25519";
+    const P: &'static str = "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffed";
+    const A: &'static str = "7fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffec";
+    const D: &'static str = "52036cee2b6ffe738cc740797779e89800700a4d4141d8ab75eb4dca135978a3";
+    const GX: &'static str = "216936d3cd6e53fec0a4e231fdd6dc5c692cc7609525a7b2c9562d608f25d51a";
+    const GY: &'static str = "6666666666666666666666666666666666666666666666666666666666666658";
+    const N: &'static str = "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+    const H: &'static str = "8";
+}
+
+/// Point in edwards25519
+pub type Edwards25519Point = EdwardsPoint<Ed25519Params>;
+
+/// How the verification equation of Ed25519 is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ed25519Verification {
+    /// check [8][S]B = [8]R + [8][k]A as recommended by RFC 8032
+    Cofactored,
+    /// check [S]B = R + [k]A, which rejects some signatures accepted by the cofactored check
+    Cofactorless,
+}
+
+/// EdDSA over edwards25519 (RFC 8032)
+///
+/// the secret, the clamped scalar and the prefix are zeroized on drop
+pub struct Ed25519 {
+    secret: [u8; 32],
+    scalar: [u8; 32],
+    prefix: [u8; 32],
+    pub pub_key: Edwards25519Point,
+    pub pub_key_bytes: [u8; 32],
+}
+
+impl Ed25519 {
+    /// Create a new Ed25519 with the 32 bytes secret key
+    ///
+    /// the secret is hashed with SHA-512, the first half is clamped into the scalar s
+    /// and the second half is the prefix used to derive the nonces
+    pub fn new(secret: [u8; 32]) -> Self {
+        let h = Sha512::digest(secret);
+        let mut scalar = [0_u8; 32];
+        scalar.copy_from_slice(&h[..32]);
+        scalar[0] &= 248;
+        scalar[31] &= 127;
+        scalar[31] |= 64;
+
+        let mut prefix = [0_u8; 32];
+        prefix.copy_from_slice(&h[32..]);
+
+        let pub_key = Edwards25519Point::generator().scalar_mul(&BigUint::from_bytes_le(&scalar));
+        let pub_key_bytes = to_array(&pub_key.compress());
+        Self {
+            secret,
+            scalar,
+            prefix,
+            pub_key,
+            pub_key_bytes,
+        }
+    }
+
+    /// Create a new Ed25519 with a random secret key
+    pub fn generate() -> Self {
+        let mut secret = [0_u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+        Self::new(secret)
+    }
+
+    /// return the 32 bytes secret key
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.secret
+    }
+
+    /// return the public key, which can verify without the secret key
+    pub fn verifying_key(&self) -> Ed25519VerifyingKey {
+        Ed25519VerifyingKey {
+            point: self.pub_key.clone(),
+            bytes: self.pub_key_bytes,
+        }
+    }
+
+    /// Sign a message, the signature is R || S
+    pub fn sign(&self, m: &[u8]) -> [u8; 64] {
+        let l = Ed25519Params::order();
+        let r = hash_to_scalar(&[&self.prefix, m]) % &l;
+        let big_r = to_array(&Edwards25519Point::generator().scalar_mul(&r).compress());
+        let k = hash_to_scalar(&[&big_r, &self.pub_key_bytes, m]) % &l;
+        let s = (r + k * self.scalar()) % &l;
+
+        let mut sig = [0_u8; 64];
+        sig[..32].copy_from_slice(&big_r);
+        sig[32..].copy_from_slice(&to_array(&s.to_bytes_le()));
+        sig
+    }
+
+    /// Verify a signature with the cofactored check
+    pub fn verify(&self, m: &[u8], sig: &[u8; 64]) -> bool {
+        Self::verify_with_key(&self.pub_key_bytes, m, sig, Ed25519Verification::Cofactored)
+    }
+
+    /// Verify a signature against an encoded public key
+    pub fn verify_with_key(
+        pub_key: &[u8; 32],
+        m: &[u8],
+        sig: &[u8; 64],
+        mode: Ed25519Verification,
+    ) -> bool {
+        let a = match Edwards25519Point::decompress(pub_key) {
+            Some(a) => a,
+            None => return false,
+        };
+        let r = match Edwards25519Point::decompress(&sig[..32]) {
+            Some(r) => r,
+            None => return false,
+        };
+        let l = Ed25519Params::order();
+        let s = BigUint::from_bytes_le(&sig[32..]);
+        if s >= l {
+            return false;
+        }
+        let k = hash_to_scalar(&[&sig[..32], pub_key, m]) % &l;
+
+        let lhs = Edwards25519Point::generator().scalar_mul(&s);
+        let rhs = r + a.scalar_mul(&k);
+        match mode {
+            Ed25519Verification::Cofactored => lhs.clear_cofactor() == rhs.clear_cofactor(),
+            Ed25519Verification::Cofactorless => lhs == rhs,
+        }
+    }
+
+    /// the clamped scalar s of the secret key
+    fn scalar(&self) -> BigUint {
+        BigUint::from_bytes_le(&self.scalar)
+    }
+}
+
+impl Drop for Ed25519 {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+        self.scalar.zeroize();
+        self.prefix.zeroize();
+    }
+}
+
+impl Signer<[u8; 64]> for Ed25519 {
+    fn sign(&self, msg: &[u8]) -> [u8; 64] {
+        Ed25519::sign(self, msg)
+    }
+}
+
+/// Ed25519 public key
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ed25519VerifyingKey {
+    point: Edwards25519Point,
+    bytes: [u8; 32],
+}
+
+impl Ed25519VerifyingKey {
+    /// decode the 32 bytes public key, return None if it is not a point of the curve
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let point = Edwards25519Point::decompress(bytes)?;
+        Some(Self {
+            point,
+            bytes: *bytes,
+        })
+    }
+
+    /// return the 32 bytes encoding of the public key
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.bytes
+    }
+
+    /// return the public point
+    pub fn as_point(&self) -> &Edwards25519Point {
+        &self.point
+    }
+
+    /// verify a signature with the given check
+    pub fn verify_with(&self, msg: &[u8], sig: &[u8; 64], mode: Ed25519Verification) -> bool {
+        Ed25519::verify_with_key(&self.bytes, msg, sig, mode)
+    }
+}
+
+impl Verifier<[u8; 64]> for Ed25519VerifyingKey {
+    /// cofactored verification
+    fn verify(&self, msg: &[u8], signature: &[u8; 64]) -> bool {
+        self.verify_with(msg, signature, Ed25519Verification::Cofactored)
+    }
+}
+
+/// SHA-512 of the concatenated parts as a little-endian integer
+fn hash_to_scalar(parts: &[&[u8]]) -> BigUint {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    BigUint::from_bytes_le(&hasher.finalize())
+}
+
+/// pad little-endian bytes to 32 bytes
+fn to_array(bytes: &[u8]) -> [u8; 32] {
+    let mut out = [0_u8; 32];
+    out[..bytes.len()].copy_from_slice(bytes);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+    use num_traits::Num;
+
+    #[test]
+    fn test_edwards25519() {
+        let p = Ed25519Params::p();
+        // d = -121665 / 121666
+        let d = Ed25519Params::field(&p - BigUint::from(121665_u32))
+            / Ed25519Params::field(BigUint::from(121666_u32));
+        assert_eq!(d, Ed25519Params::d());
+
+        let g = Edwards25519Point::generator();
+        assert!(g.scalar_mul(&Ed25519Params::order()).is_identity());
+        assert_eq!(g.double(), g.clone() + &g);
+        assert_eq!(g.clone() + Edwards25519Point::identity(), g);
+        assert!((g.clone() + (-g.clone())).is_identity());
+        assert_eq!(
+            g.scalar_mul(&BigUint::from(100_u32)) + &g,
+            g.scalar_mul(&BigUint::from(101_u32))
+        );
+    }
+
+    #[test]
+    fn test_compress() {
+        let g = Edwards25519Point::generator();
+        assert_eq!(
+            g.compress(),
+            hex("5866666666666666666666666666666666666666666666666666666666666666")
+        );
+        for n in [1_u32, 2, 3, 1000] {
+            let p = g.scalar_mul(&BigUint::from(n));
+            assert_eq!(Edwards25519Point::decompress(&p.compress()), Some(p));
+        }
+        // y = p is not canonical
+        let y = BigUint::from_str_radix(Ed25519Params::P, 16).unwrap();
+        assert_eq!(
+            Edwards25519Point::decompress(&to_array(&y.to_bytes_le())),
+            None
+        );
+    }
+
+    /// https://www.rfc-editor.org/rfc/rfc8032#section-7.1
+    #[test]
+    fn test_rfc8032() {
+        let vectors = [
+            (
+                "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+                "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+                "",
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+            ),
+            (
+                "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+                "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+                "72",
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+            ),
+            (
+                "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+                "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+                "af82",
+                "6291d657deec24024827e69c3abe01a30ce548a284743a445e3680d7db5ac3ac18ff9b538d16f290ae67f760984dc6594a7c15e9716ed28dc027beceea1ec40a",
+            ),
+        ];
+        for (secret, public, m, sig) in vectors {
+            let ed25519 = Ed25519::new(to_array(&hex(secret)));
+            assert_eq!(ed25519.pub_key_bytes.to_vec(), hex(public));
+            assert_eq!(ed25519.to_bytes().to_vec(), hex(secret));
+            let m = hex(m);
+            let signature = ed25519.sign(&m);
+            assert_eq!(signature.to_vec(), hex(sig));
+            assert!(ed25519.verify(&m, &signature));
+            assert!(Ed25519::verify_with_key(
+                &ed25519.pub_key_bytes,
+                &m,
+                &signature,
+                Ed25519Verification::Cofactorless
+            ));
+        }
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let ed25519 = Ed25519::generate();
+        let m = b"no.more.caffeine";
+        let mut sig = ed25519.sign(m);
+        assert!(ed25519.verify(m, &sig));
+        assert!(!ed25519.verify(b"no.more.tea", &sig));
+
+        // the verifier only needs the encoded public key
+        let verifying_key = Ed25519VerifyingKey::from_bytes(&ed25519.pub_key_bytes).unwrap();
+        assert_eq!(verifying_key, ed25519.verifying_key());
+        assert_eq!(verifying_key.as_point(), &ed25519.pub_key);
+        assert!(Verifier::verify(&verifying_key, m, &sig));
+        assert!(!Verifier::verify(&verifying_key, b"no.more.tea", &sig));
+
+        // S + L is rejected since S must be reduced
+        let s = BigUint::from_bytes_le(&sig[32..]) + Ed25519Params::order();
+        sig[32..].copy_from_slice(&to_array(&s.to_bytes_le()));
+        assert!(!ed25519.verify(m, &sig));
+        assert!(!Verifier::verify(&verifying_key, m, &sig));
+    }
+
+    #[test]
+    fn test_verification_modes() {
+        // adding a point T of order 8 to R passes the cofactored check only
+        let ed25519 = Ed25519::new([7_u8; 32]);
+        let m = b"small order";
+        let sig = ed25519.sign(m);
+
+        let t = Edwards25519Point::decompress(&hex(
+            "c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+        ))
+        .unwrap();
+        assert!(t.scalar_mul(&BigUint::from(8_u32)).is_identity());
+        assert!(!t.scalar_mul(&BigUint::from(4_u32)).is_identity());
+        assert!(t.is_small_order() && !t.is_torsion_free());
+        let g = Edwards25519Point::generator();
+        assert!(g.is_torsion_free() && !g.is_small_order());
+        assert!(!(g.clone() + &t).is_torsion_free());
+        assert!((g.clone() + &t).clear_cofactor().is_torsion_free());
+
+        let r = Edwards25519Point::decompress(&sig[..32]).unwrap() + t;
+        let mut forged = sig;
+        forged[..32].copy_from_slice(&r.compress());
+        // the challenge k depends on R so recompute S = r + k*s for the new R
+        let l = Ed25519Params::order();
+        let k = hash_to_scalar(&[&forged[..32], &ed25519.pub_key_bytes, m]) % &l;
+        let k_old = hash_to_scalar(&[&sig[..32], &ed25519.pub_key_bytes, m]) % &l;
+        let s_old = BigUint::from_bytes_le(&sig[32..]);
+        let s = (s_old + (&l - k_old + k) % &l * ed25519.scalar()) % &l;
+        forged[32..].copy_from_slice(&to_array(&s.to_bytes_le()));
+
+        let public = ed25519.pub_key_bytes;
+        assert!(Ed25519::verify_with_key(
+            &public,
+            m,
+            &forged,
+            Ed25519Verification::Cofactored
+        ));
+        assert!(!Ed25519::verify_with_key(
+            &public,
+            m,
+            &forged,
+            Ed25519Verification::Cofactorless
+        ));
+    }
+}
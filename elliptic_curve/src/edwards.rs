@@ -0,0 +1,301 @@
+use crate::curve::from_hex;
+use finite_field::ff::FiniteField;
+use finite_field::helper::tonelli_shanks;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::{Add, Neg};
+
+/// Parameters of a twisted edwards curve a*x^2 + y^2 = 1 + d*x^2*y^2 over F_p
+///
+/// all the numbers are given as hex strings
+pub trait EdwardsParams: Debug + Clone + PartialEq {
+    /// name of the curve
+    const NAME: &'static str;
+
+    /// prime of the base field
+    const P: &'static str;
+
+    /// coefficient a
+    const A: &'static str;
+
+    /// coefficient d
+    const D: &'static str;
+
+    /// x coordinate of the generator point
+    const GX: &'static str;
+
+    /// y coordinate of the generator point
+    const GY: &'static str;
+
+    /// order of the generator point
+    const N: &'static str;
+
+    /// cofactor h = #E(F_p) / n
+    const H: &'static str;
+
+    /// return the prime of the base field
+    fn p() -> BigUint {
+        from_hex(Self::P)
+    }
+
+    /// return the number as an element of the base field
+    fn field(num: BigUint) -> FF {
+        FF::new(num, Self::p())
+    }
+
+    /// return the coefficient a
+    fn a() -> FF {
+        Self::field(from_hex(Self::A))
+    }
+
+    /// return the coefficient d
+    fn d() -> FF {
+        Self::field(from_hex(Self::D))
+    }
+
+    /// return the generator point (x, y)
+    fn generator() -> (BigUint, BigUint) {
+        (from_hex(Self::GX), from_hex(Self::GY))
+    }
+
+    /// return the order of the generator point
+    fn order() -> BigUint {
+        from_hex(Self::N)
+    }
+
+    /// return the cofactor
+    fn cofactor() -> BigUint {
+        from_hex(Self::H)
+    }
+}
+
+/// Point in the twisted edwards curve C in extended coordinates (X : Y : Z : T)
+/// which represents the affine point (X/Z, Y/Z) with T = X*Y/Z
+///
+/// The identity is (0 : 1 : 1 : 0), there is no point at infinity
+///
+/// formulas: https://www.hyperelliptic.org/EFD/g1p/auto-twisted-extended.html
+#[derive(Debug, Clone)]
+pub struct EdwardsPoint<C: EdwardsParams> {
+    pub x: FF,
+    pub y: FF,
+    pub z: FF,
+    pub t: FF,
+    pub a: FF,
+    pub d: FF,
+    _curve: PhantomData<C>,
+}
+
+impl<C: EdwardsParams> EdwardsPoint<C> {
+    /// create a new point from affine coordinates, panic if it is not in the curve
+    pub fn new(x: BigUint, y: BigUint) -> Self {
+        let (a, d) = (C::a(), C::d());
+        let x = C::field(x);
+        let y = C::field(y);
+        let xx = x.pow(2);
+        let yy = y.pow(2);
+        let one = C::field(BigUint::one());
+        if a.clone() * &xx + &yy != one.clone() + &(d.clone() * &xx * &yy) {
+            panic!("Not in curve");
+        }
+        Self {
+            t: x.clone() * &y,
+            x,
+            y,
+            z: one,
+            a,
+            d,
+            _curve: PhantomData,
+        }
+    }
+
+    /// return the identity point (0, 1)
+    pub fn identity() -> Self {
+        Self::new(BigUint::zero(), BigUint::one())
+    }
+
+    /// return the generator point
+    pub fn generator() -> Self {
+        let (x, y) = C::generator();
+        Self::new(x, y)
+    }
+
+    /// return true if the point is the identity
+    pub fn is_identity(&self) -> bool {
+        self.x.num.is_zero() && self.y == self.z
+    }
+
+    /// return the affine coordinates (x, y)
+    pub fn xy(&self) -> (FF, FF) {
+        let z_inv = self.z.inverse();
+        (self.x.clone() * &z_inv, self.y.clone() * &z_inv)
+    }
+
+    /// point doubling
+    ///
+    /// use dbl-2008-hwcd
+    pub fn double(&self) -> Self {
+        let a = self.x.pow(2);
+        let b = self.y.pow(2);
+        let zz = self.z.pow(2);
+        let c = zz.clone() + &zz;
+        let d = self.a.clone() * &a;
+        let e = (self.x.clone() + &self.y).pow(2) - &a - &b;
+        let g = d.clone() + &b;
+        let f = g.clone() - &c;
+        let h = d - &b;
+        Self {
+            x: e.clone() * &f,
+            y: g.clone() * &h,
+            z: f * &g,
+            t: e * &h,
+            a: self.a.clone(),
+            d: self.d.clone(),
+            _curve: PhantomData,
+        }
+    }
+
+    /// return n*P with double-and-add
+    pub fn scalar_mul(&self, n: &BigUint) -> Self {
+        let mut r = Self {
+            x: self.x.to_zero(),
+            y: self.z.clone(),
+            z: self.z.clone(),
+            t: self.x.to_zero(),
+            a: self.a.clone(),
+            d: self.d.clone(),
+            _curve: PhantomData,
+        };
+        for i in (0..n.bits()).rev() {
+            r = r.double();
+            if n.bit(i) {
+                r = r + self;
+            }
+        }
+        r
+    }
+
+    /// return h*P which is in the subgroup of order n
+    pub fn clear_cofactor(&self) -> Self {
+        self.scalar_mul(&C::cofactor())
+    }
+
+    /// return true if the order of the point divides the cofactor
+    pub fn is_small_order(&self) -> bool {
+        self.clear_cofactor().is_identity()
+    }
+
+    /// return true if the point is in the subgroup of order n
+    pub fn is_torsion_free(&self) -> bool {
+        self.scalar_mul(&C::order()).is_identity()
+    }
+
+    /// encode the point as in RFC 8032 section 5.1.2
+    ///
+    /// y in little-endian with the least significant bit of x in the most significant bit
+    pub fn compress(&self) -> Vec<u8> {
+        let len = Self::encoding_len();
+        let (x, y) = self.xy();
+        let mut bytes = y.num.to_bytes_le();
+        bytes.resize(len, 0);
+        if x.num.bit(0) {
+            bytes[len - 1] |= 0x80;
+        }
+        bytes
+    }
+
+    /// decode a point encoded by compress as in RFC 8032 section 5.1.3
+    ///
+    /// return None if the encoding is not valid
+    pub fn decompress(bytes: &[u8]) -> Option<Self> {
+        let len = Self::encoding_len();
+        if bytes.len() != len {
+            return None;
+        }
+        let mut bytes = bytes.to_vec();
+        let sign = bytes[len - 1] & 0x80 != 0;
+        bytes[len - 1] &= 0x7f;
+
+        let p = C::p();
+        let y = BigUint::from_bytes_le(&bytes);
+        if y >= p {
+            return None;
+        }
+
+        // x^2 = (y^2 - 1) / (d*y^2 - a)
+        let y = C::field(y);
+        let yy = y.pow(2);
+        let one = C::field(BigUint::one());
+        let u = yy.clone() - &one;
+        let v = C::d() * &yy - &C::a();
+        let xx = u / v;
+        let x = tonelli_shanks(xx.num, p.clone())?;
+        if x.is_zero() && sign {
+            return None;
+        }
+        let x = if x.bit(0) != sign { &p - x } else { x };
+        Some(Self::new(x, y.num))
+    }
+
+    /// number of bytes of an encoded point which fits the bits of p plus one sign bit
+    fn encoding_len() -> usize {
+        (C::p().bits() as usize + 1).div_ceil(8)
+    }
+}
+
+impl<C: EdwardsParams> PartialEq for EdwardsPoint<C> {
+    /// (X1 : Y1 : Z1) = (X2 : Y2 : Z2) iff X1*Z2 = X2*Z1 and Y1*Z2 = Y2*Z1
+    fn eq(&self, other: &EdwardsPoint<C>) -> bool {
+        self.x.clone() * &other.z == other.x.clone() * &self.z
+            && self.y.clone() * &other.z == other.y.clone() * &self.z
+    }
+}
+
+impl<C: EdwardsParams> Neg for EdwardsPoint<C> {
+    type Output = Self;
+
+    /// -(x, y) = (-x, y)
+    fn neg(self) -> Self::Output {
+        Self {
+            x: self.x.to_zero() - &self.x,
+            t: self.t.to_zero() - &self.t,
+            ..self
+        }
+    }
+}
+
+impl<C: EdwardsParams> Add for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl<C: EdwardsParams> Add<&Self> for EdwardsPoint<C> {
+    type Output = Self;
+
+    /// unified addition add-2008-hwcd which is complete when a is a square and d is not
+    fn add(self, rhs: &Self) -> Self::Output {
+        let a = self.x.clone() * &rhs.x;
+        let b = self.y.clone() * &rhs.y;
+        let c = self.t.clone() * &self.d * &rhs.t;
+        let d = self.z.clone() * &rhs.z;
+        let e = (self.x.clone() + &self.y) * &(rhs.x.clone() + &rhs.y) - &a - &b;
+        let f = d.clone() - &c;
+        let g = d + &c;
+        let h = b - &(self.a.clone() * &a);
+        Self {
+            x: e.clone() * &f,
+            y: g.clone() * &h,
+            z: f * &g,
+            t: e * &h,
+            a: self.a,
+            d: self.d,
+            _curve: PhantomData,
+        }
+    }
+}
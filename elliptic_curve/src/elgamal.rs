@@ -0,0 +1,414 @@
+use crate::curve::{Curve, CurveParams, CurvePoint};
+use crate::ecdsa::SigningKey;
+use crate::secp256k1::Secp256k1Params;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::One;
+use rand::{CryptoRng, RngCore};
+use std::collections::HashMap;
+use std::iter::Sum;
+use std::ops::{Add, Mul};
+
+/// EC ElGamal ciphertext (c1, c2) = (r*G, M + r*X)
+///
+/// ciphertexts under the same key are additively homomorphic, the sum decrypts to the
+/// sum of the points and k times a ciphertext decrypts to k*M
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElGamalCiphertext<C: CurveParams = Secp256k1Params> {
+    pub c1: CurvePoint<C>,
+    pub c2: CurvePoint<C>,
+}
+
+impl<C: CurveParams> Add for ElGamalCiphertext<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self + &other
+    }
+}
+
+impl<C: CurveParams> Add<&Self> for ElGamalCiphertext<C> {
+    type Output = Self;
+
+    fn add(self, other: &Self) -> Self {
+        Self {
+            c1: self.c1 + &other.c1,
+            c2: self.c2 + &other.c2,
+        }
+    }
+}
+
+impl<C: CurveParams> Sum for ElGamalCiphertext<C> {
+    /// the sum of no ciphertext is the trivial encryption of infinity
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let zero = Self {
+            c1: CurvePoint::new_infinity_point(),
+            c2: CurvePoint::new_infinity_point(),
+        };
+        iter.fold(zero, |acc, ct| acc + ct)
+    }
+}
+
+impl<C: CurveParams> Mul<&BigUint> for &ElGamalCiphertext<C> {
+    type Output = ElGamalCiphertext<C>;
+
+    fn mul(self, k: &BigUint) -> ElGamalCiphertext<C> {
+        ElGamalCiphertext {
+            c1: &self.c1 * k,
+            c2: &self.c2 * k,
+        }
+    }
+}
+
+/// EC ElGamal public key X = x*G
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElGamalPublicKey<C: CurveParams = Secp256k1Params> {
+    point: CurvePoint<C>,
+}
+
+impl<C: CurveParams> ElGamalPublicKey<C> {
+    /// create a public key from a point, return None if it is not a valid public key
+    pub fn from_point(point: CurvePoint<C>) -> Option<Self> {
+        Curve::<C>::new()
+            .validate_public_key(&point)
+            .then_some(Self { point })
+    }
+
+    /// return the public point
+    pub fn as_point(&self) -> &CurvePoint<C> {
+        &self.point
+    }
+
+    /// encrypt the point m with a random r
+    pub fn encrypt<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        m: &CurvePoint<C>,
+    ) -> ElGamalCiphertext<C> {
+        self.encrypt_with_randomness(m, &random_scalar::<C, R>(rng))
+    }
+
+    /// encrypt the integer m as m*G, decrypt it with decrypt_u64
+    pub fn encrypt_u64<R: RngCore + CryptoRng>(&self, rng: &mut R, m: u64) -> ElGamalCiphertext<C> {
+        let m = Curve::<C>::new().g().constant_time_mul(&BigUint::from(m));
+        self.encrypt(rng, &m)
+    }
+
+    /// encrypt the point m with the given r, which proofs about the encryption need
+    pub fn encrypt_with_randomness(&self, m: &CurvePoint<C>, r: &BigUint) -> ElGamalCiphertext<C> {
+        let e = Curve::<C>::new();
+        ElGamalCiphertext {
+            c1: e.g().constant_time_mul(r),
+            c2: self.point.constant_time_mul(r) + m,
+        }
+    }
+
+    /// add a fresh encryption of infinity, the result decrypts to the same point but
+    /// cannot be linked to ct
+    pub fn rerandomize<R: RngCore + CryptoRng>(
+        &self,
+        rng: &mut R,
+        ct: &ElGamalCiphertext<C>,
+    ) -> ElGamalCiphertext<C> {
+        self.encrypt(rng, &CurvePoint::new_infinity_point()) + ct
+    }
+}
+
+/// EC ElGamal private key x
+///
+/// the secret is kept in a SigningKey, which zeroizes it on drop
+pub struct ElGamalSecretKey<C: CurveParams = Secp256k1Params> {
+    key: SigningKey<C>,
+    public_key: ElGamalPublicKey<C>,
+}
+
+impl<C: CurveParams> From<SigningKey<C>> for ElGamalSecretKey<C> {
+    fn from(key: SigningKey<C>) -> Self {
+        let point = key.verifying_key().as_point().clone();
+        Self {
+            key,
+            public_key: ElGamalPublicKey { point },
+        }
+    }
+}
+
+impl<C: CurveParams> ElGamalSecretKey<C> {
+    /// create a secret key from the big endian secret, return None if it is not in [1, n - 1]
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        SigningKey::from_bytes(bytes).map(Self::from)
+    }
+
+    /// create a random secret key
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        SigningKey::random(rng).into()
+    }
+
+    /// return the secret as big endian bytes of the length of n
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_bytes()
+    }
+
+    /// return the public key
+    pub fn public_key(&self) -> &ElGamalPublicKey<C> {
+        &self.public_key
+    }
+
+    /// return the point M = c2 - x*c1
+    pub fn decrypt(&self, ct: &ElGamalCiphertext<C>) -> CurvePoint<C> {
+        ct.c2.clone() - ct.c1.constant_time_mul(&self.key.secret_scalar())
+    }
+
+    /// decrypt an encryption of m*G, return None if m is not in the range of the table
+    pub fn decrypt_u64(
+        &self,
+        ct: &ElGamalCiphertext<C>,
+        table: &DiscreteLogTable<C>,
+    ) -> Option<u64> {
+        table.solve(&self.decrypt(ct))
+    }
+
+    /// decrypt and prove that the point is the plaintext of ct with the proof system P
+    pub fn decrypt_with_proof<P: DecryptionProof<C>>(
+        &self,
+        ct: &ElGamalCiphertext<C>,
+    ) -> (CurvePoint<C>, P) {
+        (self.decrypt(ct), P::prove(self, ct))
+    }
+}
+
+/// Proof that a ciphertext decrypts to a point under a public key
+///
+/// e.g. a Chaum-Pedersen proof that log_G(X) = log_c1(c2 - M), the scheme does not fix one
+pub trait DecryptionProof<C: CurveParams> {
+    /// prove that ct decrypts to secret.decrypt(ct)
+    fn prove(secret: &ElGamalSecretKey<C>, ct: &ElGamalCiphertext<C>) -> Self;
+
+    /// return true if ct decrypts to m under the public key
+    fn verify(
+        &self,
+        public: &ElGamalPublicKey<C>,
+        ct: &ElGamalCiphertext<C>,
+        m: &CurvePoint<C>,
+    ) -> bool;
+}
+
+/// Baby-step giant-step table to solve m*G = P for m in [0, max]
+///
+/// it stores about sqrt(max) points and solves in about sqrt(max) additions, build it
+/// once to decrypt many ciphertexts
+pub struct DiscreteLogTable<C: CurveParams = Secp256k1Params> {
+    max: u64,
+    step: u64,
+    baby_steps: HashMap<Vec<u8>, u64>,
+    giant_step: CurvePoint<C>,
+}
+
+impl<C: CurveParams> DiscreteLogTable<C> {
+    /// the largest max of a table, which then stores 2^20 points
+    pub const MAX: u64 = (1 << 40) - 1;
+
+    /// build the table of j*G for j < floor(sqrt(max)) + 1, return None if max > MAX
+    ///
+    /// when G has a smaller order than the table the multiples repeat, the smallest j is
+    /// kept so solve returns the smallest m
+    pub fn new(max: u64) -> Option<Self> {
+        if max > Self::MAX {
+            return None;
+        }
+        let e = Curve::<C>::new();
+        let step = max.isqrt() + 1;
+        let mut baby_steps = HashMap::new();
+        let mut p = e.infinity();
+        for j in 0..step {
+            baby_steps.entry(p.to_bytes(true)).or_insert(j);
+            p += e.g();
+        }
+        Some(Self {
+            max,
+            step,
+            baby_steps,
+            giant_step: -p,
+        })
+    }
+
+    /// return the smallest m in [0, max] with m*G = p
+    pub fn solve(&self, p: &CurvePoint<C>) -> Option<u64> {
+        let mut p = p.clone();
+        for i in 0..=self.max / self.step {
+            if let Some(j) = self.baby_steps.get(&p.to_bytes(true)) {
+                let m = i.checked_mul(self.step)?.checked_add(*j)?;
+                return (m <= self.max).then_some(m);
+            }
+            p += &self.giant_step;
+        }
+        None
+    }
+}
+
+fn random_scalar<C: CurveParams, R: RngCore + CryptoRng>(rng: &mut R) -> BigUint {
+    rng.gen_biguint_range(&BigUint::one(), &C::order())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p256::P256Params;
+    use crate::test_utils::Toy;
+    use sha2::{Digest, Sha256};
+
+    /// Chaum-Pedersen proof with Fiat-Shamir over SHA-256
+    struct ChaumPedersen {
+        c: BigUint,
+        z: BigUint,
+    }
+
+    fn challenge(points: &[&CurvePoint<Secp256k1Params>]) -> BigUint {
+        let mut h = Sha256::new();
+        for p in points {
+            h.update(p.to_bytes(true));
+        }
+        BigUint::from_bytes_be(&h.finalize()) % Secp256k1Params::order()
+    }
+
+    impl DecryptionProof<Secp256k1Params> for ChaumPedersen {
+        fn prove(
+            secret: &ElGamalSecretKey<Secp256k1Params>,
+            ct: &ElGamalCiphertext<Secp256k1Params>,
+        ) -> Self {
+            let n = Secp256k1Params::order();
+            let x = BigUint::from_bytes_be(&secret.to_bytes());
+            let k = random_scalar::<Secp256k1Params, _>(&mut rand::thread_rng());
+            let g = Curve::<Secp256k1Params>::new().g().clone();
+            let (a, b) = (&g * &k, &ct.c1 * &k);
+            let c = challenge(&[secret.public_key().as_point(), &ct.c1, &ct.c2, &a, &b]);
+            let z = (k + &c * x) % n;
+            Self { c, z }
+        }
+
+        fn verify(
+            &self,
+            public: &ElGamalPublicKey<Secp256k1Params>,
+            ct: &ElGamalCiphertext<Secp256k1Params>,
+            m: &CurvePoint<Secp256k1Params>,
+        ) -> bool {
+            let n = Secp256k1Params::order();
+            let g = Curve::<Secp256k1Params>::new().g().clone();
+            let neg_c = &n - &self.c;
+            let shared = ct.c2.clone() - m;
+            let a = g.shamir_mul(&self.z, public.as_point(), &neg_c);
+            let b = ct.c1.shamir_mul(&self.z, &shared, &neg_c);
+            self.c == challenge(&[public.as_point(), &ct.c1, &ct.c2, &a, &b])
+        }
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let mut rng = rand::thread_rng();
+        let key = ElGamalSecretKey::<Secp256k1Params>::random(&mut rng);
+        let m = Curve::<Secp256k1Params>::new().g() * &BigUint::from(12345_u32);
+        let ct = key.public_key().encrypt(&mut rng, &m);
+        assert_eq!(key.decrypt(&ct), m);
+        let other = ElGamalSecretKey::<Secp256k1Params>::random(&mut rng);
+        assert_ne!(other.decrypt(&ct), m);
+
+        let key = ElGamalSecretKey::<P256Params>::from_bytes(&[7]).unwrap();
+        let table = DiscreteLogTable::new(1000).unwrap();
+        let ct = key.public_key().encrypt_u64(&mut rng, 999);
+        assert_eq!(key.decrypt_u64(&ct, &table), Some(999));
+        let ct = key.public_key().encrypt_u64(&mut rng, 1001);
+        assert_eq!(key.decrypt_u64(&ct, &table), None);
+    }
+
+    #[test]
+    fn test_secret_key() {
+        let key = ElGamalSecretKey::<Secp256k1Params>::from_bytes(&[7]).unwrap();
+        let signing_key = SigningKey::<Secp256k1Params>::from_bytes(&key.to_bytes()).unwrap();
+        assert_eq!(
+            key.public_key().as_point(),
+            signing_key.verifying_key().as_point()
+        );
+        assert_eq!(
+            ElGamalSecretKey::from(signing_key).to_bytes(),
+            key.to_bytes()
+        );
+        assert!(ElGamalSecretKey::<Secp256k1Params>::from_bytes(&[0]).is_none());
+        let n = Secp256k1Params::order().to_bytes_be();
+        assert!(ElGamalSecretKey::<Secp256k1Params>::from_bytes(&n).is_none());
+    }
+
+    #[test]
+    fn test_discrete_log_table() {
+        let g = Curve::<Secp256k1Params>::new().g().clone();
+        for max in [0, 1, 2, 15, 16, 17, 100] {
+            let table = DiscreteLogTable::<Secp256k1Params>::new(max).unwrap();
+            for m in 0..=max + 2 {
+                let expected = (m <= max).then_some(m);
+                assert_eq!(table.solve(&(&g * &BigUint::from(m))), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_discrete_log_small_order() {
+        // G has order 50 and the table 101 baby steps, so j*G repeats
+        let g = Curve::<Toy>::new().g().clone();
+        let table = DiscreteLogTable::<Toy>::new(10_000).unwrap();
+        for m in [0_u32, 7, 49, 57, 9_999] {
+            let expected = Some(u64::from(m % 50));
+            assert_eq!(table.solve(&(&g * &BigUint::from(m))), expected);
+        }
+    }
+
+    #[test]
+    fn test_discrete_log_table_too_large() {
+        let max = DiscreteLogTable::<Secp256k1Params>::MAX;
+        assert!(DiscreteLogTable::<Secp256k1Params>::new(max + 1).is_none());
+        assert!(DiscreteLogTable::<Secp256k1Params>::new(u64::MAX).is_none());
+    }
+
+    #[test]
+    fn test_homomorphism() {
+        let mut rng = rand::thread_rng();
+        let key = ElGamalSecretKey::<Secp256k1Params>::random(&mut rng);
+        let pk = key.public_key();
+        let table = DiscreteLogTable::new(100).unwrap();
+        let votes = [1, 0, 1, 1, 0, 1];
+        let tally: ElGamalCiphertext<_> = votes.iter().map(|v| pk.encrypt_u64(&mut rng, *v)).sum();
+        assert_eq!(key.decrypt_u64(&tally, &table), Some(4));
+        let sum = pk.encrypt_u64(&mut rng, 20) + &pk.encrypt_u64(&mut rng, 22);
+        assert_eq!(key.decrypt_u64(&sum, &table), Some(42));
+        let scaled = &pk.encrypt_u64(&mut rng, 7) * &BigUint::from(3_u32);
+        assert_eq!(key.decrypt_u64(&scaled, &table), Some(21));
+    }
+
+    #[test]
+    fn test_rerandomize() {
+        let mut rng = rand::thread_rng();
+        let key = ElGamalSecretKey::<Secp256k1Params>::random(&mut rng);
+        let m = Curve::<Secp256k1Params>::new().g().clone();
+        let ct = key.public_key().encrypt(&mut rng, &m);
+        let rerandomized = key.public_key().rerandomize(&mut rng, &ct);
+        assert_ne!(rerandomized.c1, ct.c1);
+        assert_ne!(rerandomized.c2, ct.c2);
+        assert_eq!(key.decrypt(&rerandomized), m);
+        let r = BigUint::from(5_u32);
+        let ct = key.public_key().encrypt_with_randomness(&m, &r);
+        assert_eq!(ct.c1, Curve::<Secp256k1Params>::new().g() * &r);
+    }
+
+    #[test]
+    fn test_decryption_proof() {
+        let mut rng = rand::thread_rng();
+        let key = ElGamalSecretKey::<Secp256k1Params>::random(&mut rng);
+        let pk = key.public_key();
+        let ct = pk.encrypt_u64(&mut rng, 3);
+        let (m, proof) = key.decrypt_with_proof::<ChaumPedersen>(&ct);
+        assert_eq!(
+            m,
+            Curve::<Secp256k1Params>::new().g() * &BigUint::from(3_u32)
+        );
+        assert!(proof.verify(pk, &ct, &m));
+        assert!(!proof.verify(pk, &ct, &(m.clone() + Curve::<Secp256k1Params>::new().g())));
+        let other = ElGamalSecretKey::<Secp256k1Params>::random(&mut rng);
+        assert!(!proof.verify(other.public_key(), &ct, &m));
+    }
+}
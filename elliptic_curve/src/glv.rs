@@ -0,0 +1,182 @@
+use crate::point::ECCPoint;
+use crate::projective::ProjectivePoint;
+use crate::wnaf::straus_mul;
+use finite_field::ff::FiniteField;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// GLV endomorphism (x, y) -> (beta*x, y) which acts as multiplication by lambda
+///
+/// the vectors (a1, b1) and (a2, b2) are a short basis of the lattice of (x, y) with
+/// x + y*lambda = 0 mod n, they split k in two scalars of half the size of n.
+/// https://www.iacr.org/archive/crypto2001/21390189.pdf
+#[derive(Debug, Clone)]
+pub struct Glv {
+    pub beta: BigUint,
+    pub lambda: BigUint,
+    pub n: BigUint,
+    pub a1: BigInt,
+    pub b1: BigInt,
+    pub a2: BigInt,
+    pub b2: BigInt,
+}
+
+impl Glv {
+    /// return (beta*x, y) = lambda*P
+    pub fn endomorphism(&self, p: &ECCPoint) -> ECCPoint {
+        match &p.x {
+            Some(x) => ECCPoint {
+                x: Some(FF::new(&x.num * &self.beta, x.prime.clone())),
+                y: p.y.clone(),
+                a: p.a.clone(),
+                b: p.b.clone(),
+            },
+            None => p.clone(),
+        }
+    }
+
+    /// return (k1, k2) such that k = k1 + k2*lambda mod n and |k1|, |k2| are about sqrt(n)
+    ///
+    /// c1 = round(b2*k / n), c2 = round(-b1*k / n), k1 = k - c1*a1 - c2*a2, k2 = -c1*b1 - c2*b2
+    pub fn decompose(&self, k: &BigUint) -> (BigInt, BigInt) {
+        let n = BigInt::from(self.n.clone());
+        let k = BigInt::from(k % &self.n);
+        // both numerators are non negative since b1 < 0 < b2
+        let round = |x: BigInt| -> BigInt { (x * 2 + &n) / (&n * 2) };
+        let c1 = round(&self.b2 * &k);
+        let c2 = round(-&self.b1 * &k);
+        let k1 = k - &c1 * &self.a1 - &c2 * &self.a2;
+        let k2 = -(c1 * &self.b1) - c2 * &self.b2;
+        (k1, k2)
+    }
+
+    /// maximal number of bits of k1 and k2
+    pub fn bits(&self) -> u64 {
+        self.n.bits().div_ceil(2) + 1
+    }
+
+    /// k*P with Straus over P and lambda*P, this halves the doublings
+    pub fn mul(&self, p: &ECCPoint, k: &BigUint) -> ECCPoint {
+        let (points, scalars) = self.split(p, k);
+        straus_mul(&points, &scalars).unwrap()
+    }
+
+    /// k1*P + k2*Q with Straus over P, lambda*P, Q and lambda*Q
+    pub fn shamir_mul(&self, p: &ECCPoint, k1: &BigUint, q: &ECCPoint, k2: &BigUint) -> ECCPoint {
+        let (mut points, mut scalars) = self.split(p, k1);
+        let (q_points, q_scalars) = self.split(q, k2);
+        points.extend(q_points);
+        scalars.extend(q_scalars);
+        straus_mul(&points, &scalars).expect("not in the same curve")
+    }
+
+    /// k*P for a secret k, a ladder over the joint bits of k1 and k2 with complete formulas
+    ///
+    /// every step is one doubling and one addition of a point selected from
+    /// [O, P, lambda*P, P + lambda*P] with conditional swaps, the signs of k1 and k2 are
+    /// also applied with conditional swaps
+    pub fn constant_time_mul(&self, p: &ECCPoint, k: &BigUint) -> ECCPoint {
+        let (k1, k2) = self.decompose(k);
+        let p1 = conditional_neg(ProjectivePoint::from_affine(p), k1.sign() == Sign::Minus);
+        let p2 = conditional_neg(
+            ProjectivePoint::from_affine(&self.endomorphism(p)),
+            k2.sign() == Sign::Minus,
+        );
+        let (k1, k2) = (k1.magnitude(), k2.magnitude());
+        let infinity = ProjectivePoint::new_infinity_point(p.a.clone(), p.b.clone());
+        let table = [
+            infinity.clone(),
+            p1.clone(),
+            p2.clone(),
+            p1.add_complete(&p2),
+        ];
+
+        let mut r = infinity;
+        for i in (0..self.bits()).rev() {
+            let index = k1.bit(i) as usize | (k2.bit(i) as usize) << 1;
+            let mut selected = table[0].clone();
+            for (j, entry) in table.iter().enumerate().skip(1) {
+                let mut entry = entry.clone();
+                ProjectivePoint::conditional_swap(&mut selected, &mut entry, (index == j) as u8);
+            }
+            r = r.add_complete(&r).add_complete(&selected);
+        }
+        r.to_affine()
+    }
+
+    /// return [±P, ±lambda*P] and [|k1|, |k2|]
+    fn split(&self, p: &ECCPoint, k: &BigUint) -> (Vec<ECCPoint>, Vec<BigUint>) {
+        let (k1, k2) = self.decompose(k);
+        let signed = |p: ECCPoint, k: &BigInt| match k.sign() {
+            Sign::Minus => -p,
+            _ => p,
+        };
+        let points = vec![signed(p.clone(), &k1), signed(self.endomorphism(p), &k2)];
+        (points, vec![k1.magnitude().clone(), k2.magnitude().clone()])
+    }
+}
+
+/// return -P if choice is true and P otherwise, without branching
+fn conditional_neg(p: ProjectivePoint, choice: bool) -> ProjectivePoint {
+    let mut neg = ProjectivePoint {
+        y: p.y.to_zero() - &p.y,
+        ..p.clone()
+    };
+    let mut p = p;
+    ProjectivePoint::conditional_swap(&mut p, &mut neg, choice as u8);
+    p
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curve::CurveParams;
+    use crate::secp256k1::{Secp256k1, Secp256k1Params};
+    use num_bigint::{BigInt, BigUint, RandBigInt};
+    use num_traits::{One, Zero};
+
+    #[test]
+    fn test_endomorphism() {
+        let glv = Secp256k1Params::glv().unwrap();
+        let g = Secp256k1::new().g().as_ecc().clone();
+        assert_eq!(glv.endomorphism(&g), g.scalar_mul(glv.lambda.clone()));
+        assert_eq!(
+            glv.lambda.modpow(&BigUint::from(3_u32), &glv.n),
+            BigUint::one()
+        );
+    }
+
+    #[test]
+    fn test_decompose() {
+        let mut rng = rand::thread_rng();
+        let glv = Secp256k1Params::glv().unwrap();
+        let n = BigInt::from(glv.n.clone());
+        let mut scalars: Vec<BigUint> = (0..200).map(|_| rng.gen_biguint_below(&glv.n)).collect();
+        scalars.extend([BigUint::zero(), BigUint::one(), &glv.n - BigUint::one()]);
+        for k in scalars {
+            let (k1, k2) = glv.decompose(&k);
+            let sum = (&k1 + &k2 * BigInt::from(glv.lambda.clone()) - BigInt::from(k)) % &n;
+            assert!(sum.is_zero());
+            assert!(k1.bits() <= 129 && k2.bits() <= 129);
+        }
+    }
+
+    #[test]
+    fn test_glv_mul() {
+        let mut rng = rand::thread_rng();
+        let glv = Secp256k1Params::glv().unwrap();
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let q = g.scalar_mul(rng.gen_biguint(256));
+        for _ in 0..5 {
+            let (k1, k2) = (rng.gen_biguint(256), rng.gen_biguint(256));
+            let expected = g.scalar_mul(k1.clone());
+            assert_eq!(glv.mul(&g, &k1), expected);
+            assert_eq!(glv.constant_time_mul(&g, &k1), expected);
+            assert_eq!(
+                glv.shamir_mul(&g, &k1, &q, &k2),
+                expected + q.scalar_mul(k2.clone())
+            );
+        }
+        assert!(glv.mul(&g, &glv.n).is_infinity());
+        assert!(glv.constant_time_mul(&g, &BigUint::zero()).is_infinity());
+    }
+}
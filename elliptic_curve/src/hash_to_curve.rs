@@ -0,0 +1,403 @@
+use crate::curve::{from_hex, CurveParams, CurvePoint};
+use crate::point::ECCPoint;
+use finite_field::ff::FiniteField;
+use finite_field::helper::tonelli_shanks;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use sha2::{Digest, Sha256};
+
+/// Parameters of the RFC 9380 suite C_XMD:SHA-256_SSWU for the curve C
+///
+/// https://www.rfc-editor.org/rfc/rfc9380.html
+pub trait HashToCurveParams: CurveParams {
+    /// target security level k in bits
+    const K: u64 = 128;
+
+    /// the non-square Z of the simplified SWU map, given modulo p
+    const Z: &'static str;
+
+    /// coefficient a' of the curve where the SWU map lands, a when a*b != 0
+    const SSWU_A: &'static str = Self::A;
+
+    /// coefficient b' of the curve where the SWU map lands, b when a*b != 0
+    const SSWU_B: &'static str = Self::B;
+
+    /// coefficients of the isogeny to C, lowest degree first, empty if there is none
+    ///
+    /// x = x_num(x') / x_den(x'), y = y' * y_num(x') / y_den(x')
+    const ISO_X_NUM: &'static [&'static str] = &[];
+    const ISO_X_DEN: &'static [&'static str] = &[];
+    const ISO_Y_NUM: &'static [&'static str] = &[];
+    const ISO_Y_DEN: &'static [&'static str] = &[];
+}
+
+/// expand_message_xmd with SHA-256, RFC 9380 section 5.3.1
+///
+/// return len_in_bytes uniformly random bytes derived from msg and dst
+pub fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    const B_IN_BYTES: usize = 32;
+    const S_IN_BYTES: usize = 64;
+    let ell = len_in_bytes.div_ceil(B_IN_BYTES);
+    if ell > 255 || len_in_bytes > u16::MAX as usize {
+        panic!("len_in_bytes is too large");
+    }
+    // a DST longer than 255 bytes is replaced by its hash, section 5.3.3
+    let dst = if dst.len() > 255 {
+        Sha256::new()
+            .chain_update(b"H2C-OVERSIZE-DST-")
+            .chain_update(dst)
+            .finalize()
+            .to_vec()
+    } else {
+        dst.to_vec()
+    };
+    let mut dst_prime = dst;
+    dst_prime.push(dst_prime.len() as u8);
+
+    let b_0 = Sha256::new()
+        .chain_update([0_u8; S_IN_BYTES])
+        .chain_update(msg)
+        .chain_update((len_in_bytes as u16).to_be_bytes())
+        .chain_update([0_u8])
+        .chain_update(&dst_prime)
+        .finalize();
+
+    let mut uniform_bytes = Vec::with_capacity(ell * B_IN_BYTES);
+    let mut b_i = Sha256::new()
+        .chain_update(b_0)
+        .chain_update([1_u8])
+        .chain_update(&dst_prime)
+        .finalize();
+    uniform_bytes.extend_from_slice(&b_i);
+    for i in 2..=ell {
+        let xor: Vec<u8> = b_0.iter().zip(&b_i).map(|(a, b)| a ^ b).collect();
+        b_i = Sha256::new()
+            .chain_update(xor)
+            .chain_update([i as u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
+
+/// hash_to_field, RFC 9380 section 5.2
+///
+/// return count elements of F_p, each one from L = ceil((ceil(log2(p)) + k) / 8) bytes
+pub fn hash_to_field<C: HashToCurveParams>(msg: &[u8], dst: &[u8], count: usize) -> Vec<FF> {
+    let p = C::p();
+    let l = (p.bits() + C::K).div_ceil(8) as usize;
+    expand_message_xmd(msg, dst, count * l)
+        .chunks(l)
+        .map(|bytes| C::field(BigUint::from_bytes_be(bytes)))
+        .collect()
+}
+
+/// simplified SWU map, RFC 9380 section 6.6.2
+///
+/// return a point of y^2 = x^3 + a'x + b', this is C itself when there is no isogeny
+pub fn map_to_curve_sswu<C: HashToCurveParams>(u: &FF) -> ECCPoint {
+    let (a, b, z) = (
+        C::field(from_hex(C::SSWU_A)),
+        C::field(from_hex(C::SSWU_B)),
+        C::field(from_hex(C::Z)),
+    );
+    let one = C::field(BigUint::one());
+    let g = |x: &FF| x.pow(3) + &(a.clone() * x) + &b;
+
+    let zu2 = z.clone() * &u.pow(2);
+    let den = zu2.pow(2) + &zu2;
+    let x1 = if den.num.is_zero() {
+        b.clone() / &(z * &a)
+    } else {
+        (a.to_zero() - &b) / &a * &(one + &den.inverse())
+    };
+    let x2 = zu2 * &x1;
+    let (x, y) = match sqrt(&g(&x1)) {
+        Some(y) => (x1, y),
+        None => {
+            let y = sqrt(&g(&x2)).unwrap();
+            (x2, y)
+        }
+    };
+    let y = if sgn0(u) != sgn0(&y) {
+        y.to_zero() - &y
+    } else {
+        y
+    };
+    ECCPoint {
+        x: Some(x),
+        y: Some(y),
+        a,
+        b,
+    }
+}
+
+/// map a field element to a point of C with the SWU map and the isogeny
+pub fn map_to_curve<C: HashToCurveParams>(u: &FF) -> CurvePoint<C> {
+    let point = map_to_curve_sswu::<C>(u);
+    if C::ISO_X_NUM.is_empty() {
+        return CurvePoint::from_ecc(point);
+    }
+    let (x, y) = (point.x.unwrap(), point.y.unwrap());
+    let eval = |coeffs: &[&str]| {
+        coeffs
+            .iter()
+            .rev()
+            .fold(x.to_zero(), |acc, c| acc * &x + &C::field(from_hex(c)))
+    };
+    let (x_den, y_den) = (eval(C::ISO_X_DEN), eval(C::ISO_Y_DEN));
+    // the isogeny sends the points where a denominator vanishes to infinity
+    if x_den.num.is_zero() || y_den.num.is_zero() {
+        return CurvePoint::new_infinity_point();
+    }
+    CurvePoint::from_ecc(ECCPoint {
+        x: Some(eval(C::ISO_X_NUM) / &x_den),
+        y: Some(y * &eval(C::ISO_Y_NUM) / &y_den),
+        a: C::a(),
+        b: C::b(),
+    })
+}
+
+/// hash_to_curve, RFC 9380 section 3
+///
+/// the random oracle encoding, the output is uniformly distributed in the subgroup
+pub fn hash_to_curve<C: HashToCurveParams>(msg: &[u8], dst: &[u8]) -> CurvePoint<C> {
+    let u = hash_to_field::<C>(msg, dst, 2);
+    (map_to_curve::<C>(&u[0]) + map_to_curve::<C>(&u[1])).clear_cofactor()
+}
+
+/// encode_to_curve, RFC 9380 section 3
+///
+/// the nonuniform encoding, cheaper than hash_to_curve but only covers part of the points
+pub fn encode_to_curve<C: HashToCurveParams>(msg: &[u8], dst: &[u8]) -> CurvePoint<C> {
+    let u = hash_to_field::<C>(msg, dst, 1);
+    map_to_curve::<C>(&u[0]).clear_cofactor()
+}
+
+/// sgn0 for a prime field, the parity of x
+fn sgn0(x: &FF) -> bool {
+    x.num.bit(0)
+}
+
+fn sqrt(x: &FF) -> Option<FF> {
+    tonelli_shanks(x.num.clone(), x.prime.clone()).map(|y| FF::new(y, x.prime.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::p256::P256Params;
+    use crate::point::{to_fixed_bytes, Point};
+    use crate::secp256k1::Secp256k1Params;
+    use crate::test_utils::hex;
+
+    fn bytes(x: &FF) -> Vec<u8> {
+        to_fixed_bytes(&x.num, 32)
+    }
+
+    #[test]
+    fn test_expand_message_xmd() {
+        // RFC 9380 appendix K.1
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let vectors: [(&[u8], usize, &str); 5] = [
+            (b"", 0x20, "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235"),
+            (b"abc", 0x20, "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615"),
+            (
+                b"abcdef0123456789",
+                0x20,
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+            ),
+            (
+                b"",
+                0x80,
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec849469b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7fa5b646c993f0ced",
+            ),
+            (
+                b"abc",
+                0x80,
+                "abba86a6129e366fc877aab32fc4ffc70120d8996c88aee2fe4b32d6c7b6437a647e6c3163d40b76a73cf6a5674ef1d890f95b664ee0afa5359a5c4e07985635bbecbac65d747d3d2da7ec2b8221b17b0ca9dc8a1ac1c07ea6a1e60583e2cb00058e77b7b72a298425cd1b941ad4ec65e8afc50303a22c0f99b0509b4c895f40",
+            ),
+        ];
+        for (msg, len, expected) in vectors {
+            assert_eq!(expand_message_xmd(msg, dst, len), hex(expected));
+        }
+    }
+
+    /// RFC 9380 appendix J: msg, u0, u1, Q0, Q1, P
+    fn check_vectors<C: HashToCurveParams>(dst: &[u8], vectors: &[(&[u8], [&str; 8])]) {
+        for (msg, [u0, u1, q0x, q0y, q1x, q1y, px, py]) in vectors {
+            let u = hash_to_field::<C>(msg, dst, 2);
+            assert_eq!(bytes(&u[0]), hex(u0));
+            assert_eq!(bytes(&u[1]), hex(u1));
+            for (u, x, y) in [(&u[0], q0x, q0y), (&u[1], q1x, q1y)] {
+                let q = map_to_curve::<C>(u);
+                assert_eq!((bytes(&q.x()), bytes(&q.y())), (hex(x), hex(y)));
+            }
+            let p = hash_to_curve::<C>(msg, dst);
+            assert_eq!((bytes(&p.x()), bytes(&p.y())), (hex(px), hex(py)));
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_hash_to_curve() {
+        check_vectors::<Secp256k1Params>(
+            b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_",
+            &[
+                (
+                    b"",
+                    [
+                        "6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3",
+                        "1ae6c212e08fe1a5937f6202f929a2cc8ef4ee5b9782db68b0d5799fd8f09e16",
+                        "74519ef88b32b425a095e4ebcc84d81b64e9e2c2675340a720bb1a1857b99f1e",
+                        "c174fa322ab7c192e11748beed45b508e9fdb1ce046dee9c2cd3a2a86b410936",
+                        "44548adb1b399263ded3510554d28b4bead34b8cf9a37b4bd0bd2ba4db87ae63",
+                        "96eb8e2faf05e368efe5957c6167001760233e6dd2487516b46ae725c4cce0c6",
+                        "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                        "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+                    ],
+                ),
+                (
+                    b"abc",
+                    [
+                        "128aab5d3679a1f7601e3bdf94ced1f43e491f544767e18a4873f397b08a2b61",
+                        "5897b65da3b595a813d0fdcc75c895dc531be76a03518b044daaa0f2e4689e00",
+                        "07dd9432d426845fb19857d1b3a91722436604ccbbbadad8523b8fc38a5322d7",
+                        "604588ef5138cffe3277bbd590b8550bcbe0e523bbaf1bed4014a467122eb33f",
+                        "e9ef9794d15d4e77dde751e06c182782046b8dac05f8491eb88764fc65321f78",
+                        "cb07ce53670d5314bf236ee2c871455c562dd76314aa41f012919fe8e7f717b3",
+                        "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                        "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+                    ],
+                ),
+                (
+                    b"abcdef0123456789",
+                    [
+                        "ea67a7c02f2cd5d8b87715c169d055a22520f74daeb080e6180958380e2f98b9",
+                        "7434d0d1a500d38380d1f9615c021857ac8d546925f5f2355319d823a478da18",
+                        "576d43ab0260275adf11af990d130a5752704f79478628761720808862544b5d",
+                        "643c4a7fb68ae6cff55edd66b809087434bbaff0c07f3f9ec4d49bb3c16623c3",
+                        "f89d6d261a5e00fe5cf45e827b507643e67c2a947a20fd9ad71039f8b0e29ff8",
+                        "b33855e0cc34a9176ead91c6c3acb1aacb1ce936d563bc1cee1dcffc806caf57",
+                        "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+                        "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+                    ],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_p256_hash_to_curve() {
+        check_vectors::<P256Params>(
+            b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_",
+            &[
+                (
+                    b"",
+                    [
+                        "ad5342c66a6dd0ff080df1da0ea1c04b96e0330dd89406465eeba11582515009",
+                        "8c0f1d43204bd6f6ea70ae8013070a1518b43873bcd850aafa0a9e220e2eea5a",
+                        "ab640a12220d3ff283510ff3f4b1953d09fad35795140b1c5d64f313967934d5",
+                        "dccb558863804a881d4fff3455716c836cef230e5209594ddd33d85c565b19b1",
+                        "51cce63c50d972a6e51c61334f0f4875c9ac1cd2d3238412f84e31da7d980ef5",
+                        "b45d1a36d00ad90e5ec7840a60a4de411917fbe7c82c3949a6e699e5a1b66aac",
+                        "2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4",
+                        "8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415",
+                    ],
+                ),
+                (
+                    b"abc",
+                    [
+                        "afe47f2ea2b10465cc26ac403194dfb68b7f5ee865cda61e9f3e07a537220af1",
+                        "379a27833b0bfe6f7bdca08e1e83c760bf9a338ab335542704edcd69ce9e46e0",
+                        "5219ad0ddef3cc49b714145e91b2f7de6ce0a7a7dc7406c7726c7e373c58cb48",
+                        "7950144e52d30acbec7b624c203b1996c99617d0b61c2442354301b191d93ecf",
+                        "019b7cb4efcfeaf39f738fe638e31d375ad6837f58a852d032ff60c69ee3875f",
+                        "589a62d2b22357fed5449bc38065b760095ebe6aeac84b01156ee4252715446e",
+                        "0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f",
+                        "5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e",
+                    ],
+                ),
+            ],
+        );
+    }
+
+    /// RFC 9380 appendix J NU suites: msg, u, P, Q equals P as the cofactor is 1
+    fn check_encode_vectors<C: HashToCurveParams>(dst: &[u8], vectors: &[(&[u8], [&str; 3])]) {
+        for (msg, [u0, px, py]) in vectors {
+            let u = hash_to_field::<C>(msg, dst, 1);
+            assert_eq!(bytes(&u[0]), hex(u0));
+            let p = encode_to_curve::<C>(msg, dst);
+            assert_eq!((bytes(&p.x()), bytes(&p.y())), (hex(px), hex(py)));
+            assert_eq!(p, map_to_curve::<C>(&u[0]));
+        }
+    }
+
+    #[test]
+    fn test_secp256k1_encode_to_curve() {
+        check_encode_vectors::<Secp256k1Params>(
+            b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_NU_",
+            &[
+                (
+                    b"",
+                    [
+                        "0137fcd23bc3da962e8808f97474d097a6c8aa2881fceef4514173635872cf3b",
+                        "a4792346075feae77ac3b30026f99c1441b4ecf666ded19b7522cf65c4c55c5b",
+                        "62c59e2a6aeed1b23be5883e833912b08ba06be7f57c0e9cdc663f31639ff3a7",
+                    ],
+                ),
+                (
+                    b"abc",
+                    [
+                        "e03f894b4d7caf1a50d6aa45cac27412c8867a25489e32c5ddeb503229f63a2e",
+                        "3f3b5842033fff837d504bb4ce2a372bfeadbdbd84a1d2b678b6e1d7ee426b9d",
+                        "902910d1fef15d8ae2006fc84f2a5a7bda0e0407dc913062c3a493c4f5d876a5",
+                    ],
+                ),
+                (
+                    b"abcdef0123456789",
+                    [
+                        "e7a6525ae7069ff43498f7f508b41c57f80563c1fe4283510b322446f32af41b",
+                        "07644fa6281c694709f53bdd21bed94dab995671e4a8cd1904ec4aa50c59bfdf",
+                        "c79f8d1dad79b6540426922f7fbc9579c3018dafeffcd4552b1626b506c21e7b",
+                    ],
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_p256_encode_to_curve() {
+        check_encode_vectors::<P256Params>(
+            b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_NU_",
+            &[
+                (
+                    b"",
+                    [
+                        "b22d487045f80e9edcb0ecc8d4bf77833e2bf1f3a54004d7df1d57f4802d311f",
+                        "f871caad25ea3b59c16cf87c1894902f7e7b2c822c3d3f73596c5ace8ddd14d1",
+                        "87b9ae23335bee057b99bac1e68588b18b5691af476234b8971bc4f011ddc99b",
+                    ],
+                ),
+                (
+                    b"abc",
+                    [
+                        "c7f96eadac763e176629b09ed0c11992225b3a5ae99479760601cbd69c221e58",
+                        "fc3f5d734e8dce41ddac49f47dd2b8a57257522a865c124ed02b92b5237befa4",
+                        "fe4d197ecf5a62645b9690599e1d80e82c500b22ac705a0b421fac7b47157866",
+                    ],
+                ),
+                (
+                    b"abcdef0123456789",
+                    [
+                        "314e8585fa92068b3ea2c3bab452d4257b38be1c097d58a21890456c2929614d",
+                        "f164c6674a02207e414c257ce759d35eddc7f55be6d7f415e2cc177e5d8faa84",
+                        "3aa274881d30db70485368c0467e97da0e73c18c1d00f34775d012b6fcee7f97",
+                    ],
+                ),
+            ],
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use crate::point::{batch_normalize, small, ECCPoint, ZCoordinates};
+use finite_field::ff::FiniteField;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::ops::Add;
+
+/// Point in jacobian coordinates (X : Y : Z) which represents the affine point (X/Z^2, Y/Z^3)
+///
+/// The point at infinity is (1 : 1 : 0)
+///
+/// formulas: https://www.hyperelliptic.org/EFD/g1p/auto-shortw-jacobian.html
+#[derive(Debug, Clone)]
+pub struct JacobianPoint {
+    pub x: FF,
+    pub y: FF,
+    pub z: FF,
+    pub a: FF,
+    pub b: FF,
+}
+
+impl JacobianPoint {
+    /// return infinity point
+    pub fn new_infinity_point(a: FF, b: FF) -> Self {
+        let prime = a.prime.clone();
+        Self {
+            x: FF::new(BigUint::one(), prime.clone()),
+            y: FF::new(BigUint::one(), prime.clone()),
+            z: FF::zero(prime),
+            a,
+            b,
+        }
+    }
+
+    /// convert an affine point to jacobian coordinates with Z = 1
+    pub fn from_affine(p: &ECCPoint) -> Self {
+        if p.is_infinity() {
+            return Self::new_infinity_point(p.a.clone(), p.b.clone());
+        }
+        Self {
+            x: p.x.clone().unwrap(),
+            y: p.y.clone().unwrap(),
+            z: FF::new(BigUint::one(), p.a.prime.clone()),
+            a: p.a.clone(),
+            b: p.b.clone(),
+        }
+    }
+
+    /// convert back to affine coordinates, this costs one field inversion
+    pub fn to_affine(&self) -> ECCPoint {
+        if self.is_infinity() {
+            return ECCPoint::new_infinity_point(self.a.clone(), self.b.clone());
+        }
+        let z_inv = self.z.inverse();
+        self.to_affine_with_inverse(&z_inv)
+    }
+
+    /// convert a list of points to affine coordinates with a single field inversion
+    pub fn batch_normalize(points: &[Self]) -> Vec<ECCPoint> {
+        batch_normalize(points)
+    }
+
+    /// return true if the point is infinity
+    pub fn is_infinity(&self) -> bool {
+        self.z.num.is_zero()
+    }
+
+    /// point doubling
+    ///
+    /// use dbl-2009-l when a = 0 and dbl-2007-bl otherwise
+    pub fn double(&self) -> Self {
+        if self.is_infinity() || self.y.num.is_zero() {
+            return Self::new_infinity_point(self.a.clone(), self.b.clone());
+        }
+
+        let (x3, y3, z3) = if self.a.num.is_zero() {
+            let a = self.x.pow(2);
+            let b = self.y.pow(2);
+            let c = b.pow(2);
+            let t = (self.x.clone() + &b).pow(2) - &a - &c;
+            let d = t.clone() + &t;
+            let e = a.clone() + &a + &a;
+            let f = e.pow(2);
+            let x3 = f - &d - &d;
+            let c8 = small(8, &self.a.prime) * &c;
+            let y3 = e * &(d - &x3) - &c8;
+            let yz = self.y.clone() * &self.z;
+            let z3 = yz.clone() + &yz;
+            (x3, y3, z3)
+        } else {
+            let xx = self.x.pow(2);
+            let yy = self.y.pow(2);
+            let yyyy = yy.pow(2);
+            let zz = self.z.pow(2);
+            let t = (self.x.clone() + &yy).pow(2) - &xx - &yyyy;
+            let s = t.clone() + &t;
+            let m = xx.clone() + &xx + &xx + &(self.a.clone() * &zz.pow(2));
+            let x3 = m.pow(2) - &s - &s;
+            let yyyy8 = small(8, &self.a.prime) * &yyyy;
+            let y3 = m * &(s - &x3) - &yyyy8;
+            let z3 = (self.y.clone() + &self.z).pow(2) - &yy - &zz;
+            (x3, y3, z3)
+        };
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+
+    /// mixed addition of a jacobian point and an affine point (Z2 = 1)
+    ///
+    /// use madd-2007-bl
+    pub fn add_mixed(&self, rhs: &ECCPoint) -> Self {
+        if self.a != rhs.a || self.b != rhs.b {
+            panic!("not in the same curve")
+        }
+        if rhs.is_infinity() {
+            return self.clone();
+        }
+        if self.is_infinity() {
+            return Self::from_affine(rhs);
+        }
+
+        let x2 = rhs.x.as_ref().unwrap();
+        let y2 = rhs.y.as_ref().unwrap();
+
+        let z1z1 = self.z.pow(2);
+        let u2 = x2.clone() * &z1z1;
+        let s2 = y2.clone() * &self.z * &z1z1;
+        let h = u2 - &self.x;
+        let t = s2 - &self.y;
+
+        if h.num.is_zero() {
+            if t.num.is_zero() {
+                return self.double();
+            }
+            return Self::new_infinity_point(self.a.clone(), self.b.clone());
+        }
+
+        let hh = h.pow(2);
+        let i = hh.clone() + &hh + &hh + &hh;
+        let j = h.clone() * &i;
+        let r = t.clone() + &t;
+        let v = self.x.clone() * &i;
+        let x3 = r.pow(2) - &j - &v - &v;
+        let y1j = self.y.clone() * &j;
+        let y3 = r * &(v - &x3) - &y1j - &y1j;
+        let z3 = (self.z.clone() + &h).pow(2) - &z1z1 - &hh;
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl ZCoordinates for JacobianPoint {
+    fn z(&self) -> &FF {
+        &self.z
+    }
+
+    fn affine_infinity(&self) -> ECCPoint {
+        ECCPoint::new_infinity_point(self.a.clone(), self.b.clone())
+    }
+
+    fn to_affine_with_inverse(&self, z_inv: &FF) -> ECCPoint {
+        let z_inv2 = z_inv.pow(2);
+        let z_inv3 = z_inv2.clone() * z_inv;
+        ECCPoint {
+            x: Some(self.x.clone() * &z_inv2),
+            y: Some(self.y.clone() * &z_inv3),
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl PartialEq for JacobianPoint {
+    /// (X1 : Y1 : Z1) = (X2 : Y2 : Z2) iff X1*Z2^2 = X2*Z1^2 and Y1*Z2^3 = Y2*Z1^3
+    fn eq(&self, other: &JacobianPoint) -> bool {
+        if self.a != other.a || self.b != other.b {
+            return false;
+        }
+        match (self.is_infinity(), other.is_infinity()) {
+            (true, true) => true,
+            (false, false) => {
+                let z1z1 = self.z.pow(2);
+                let z2z2 = other.z.pow(2);
+                self.x.clone() * &z2z2 == other.x.clone() * &z1z1
+                    && self.y.clone() * &z2z2 * &other.z == other.y.clone() * &z1z1 * &self.z
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Add for JacobianPoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl Add<&Self> for JacobianPoint {
+    type Output = Self;
+
+    /// use add-2007-bl
+    fn add(self, rhs: &Self) -> Self::Output {
+        if self.a != rhs.a || self.b != rhs.b {
+            panic!("not in the same curve")
+        }
+        if self.is_infinity() {
+            return rhs.clone();
+        }
+        if rhs.is_infinity() {
+            return self;
+        }
+
+        let z1z1 = self.z.pow(2);
+        let z2z2 = rhs.z.pow(2);
+        let u1 = self.x.clone() * &z2z2;
+        let u2 = rhs.x.clone() * &z1z1;
+        let s1 = self.y.clone() * &rhs.z * &z2z2;
+        let s2 = rhs.y.clone() * &self.z * &z1z1;
+        let h = u2 - &u1;
+        let t = s2 - &s1;
+
+        if h.num.is_zero() {
+            if t.num.is_zero() {
+                return self.double();
+            }
+            return Self::new_infinity_point(self.a, self.b);
+        }
+
+        let h2 = h.clone() + &h;
+        let i = h2.pow(2);
+        let j = h.clone() * &i;
+        let r = t.clone() + &t;
+        let v = u1 * &i;
+        let x3 = r.pow(2) - &j - &v - &v;
+        let s1j = s1 * &j;
+        let y3 = r * &(v - &x3) - &s1j - &s1j;
+        let z3 = ((self.z.clone() + &rhs.z).pow(2) - &z1z1 - &z2z2) * &h;
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            a: self.a,
+            b: self.b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+    use crate::test_utils::small_curve_point;
+
+    #[test]
+    fn test_double() {
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let pg = JacobianPoint::from_affine(&g);
+        assert_eq!(pg.double().to_affine(), g.clone() + &g);
+
+        let q = small_curve_point();
+        let pq = JacobianPoint::from_affine(&q);
+        assert_eq!(pq.double().to_affine(), q.clone() + &q);
+        assert_eq!(
+            pq.double().double().to_affine(),
+            q.scalar_mul(BigUint::from(4_u32))
+        );
+    }
+
+    #[test]
+    fn test_addition() {
+        for g in [Secp256k1::new().g().as_ecc().clone(), small_curve_point()] {
+            let g2 = g.clone() + &g;
+            let g3 = g2.clone() + &g;
+            let pg = JacobianPoint::from_affine(&g);
+            let pg2 = pg.double();
+
+            assert_eq!((pg2.clone() + &pg).to_affine(), g3);
+            assert_eq!(pg2.add_mixed(&g).to_affine(), g3);
+            assert_eq!(pg.add_mixed(&g), pg2);
+            assert_eq!(pg.clone() + &pg, pg2);
+
+            let inf = JacobianPoint::new_infinity_point(g.a.clone(), g.b.clone());
+            assert_eq!(inf.clone() + &pg, pg);
+            assert_eq!(pg.clone() + &inf, pg);
+            assert_eq!(inf.add_mixed(&g), pg);
+            assert!(pg.add_mixed(&-&g).is_infinity());
+        }
+    }
+
+    #[test]
+    fn test_batch_normalize() {
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let pg = JacobianPoint::from_affine(&g);
+        let inf = JacobianPoint::new_infinity_point(g.a.clone(), g.b.clone());
+        let points = vec![pg.double(), inf, pg.double().add_mixed(&g), pg.clone()];
+        let affine = JacobianPoint::batch_normalize(&points);
+        let expected: Vec<ECCPoint> = points.iter().map(|p| p.to_affine()).collect();
+        assert_eq!(affine, expected);
+        assert!(affine[1].is_infinity());
+        assert!(JacobianPoint::batch_normalize(&[]).is_empty());
+    }
+}
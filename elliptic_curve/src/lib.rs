@@ -1,7 +1,61 @@
+pub mod bls12_381;
+pub mod bn254;
+pub mod curve;
+pub mod curve25519;
+pub mod curve448;
+pub mod ecdh;
 pub mod ecdsa;
+pub mod ecies;
+pub mod ed25519;
+pub mod edwards;
+pub mod elgamal;
+pub mod glv;
+pub mod hash_to_curve;
+pub mod jacobian;
+pub mod montgomery;
+pub mod msm;
+pub mod p256;
+pub mod p384;
+pub mod p521;
+pub mod pairing;
+pub mod pedersen;
 pub mod point;
+pub mod projective;
+pub mod rfc6979;
+pub mod scalar;
+pub mod schnorr;
 pub mod secp256k1;
+pub mod signature;
+#[cfg(test)]
+mod test_utils;
+pub mod wnaf;
 
+pub use bls12_381::*;
+pub use bn254::*;
+pub use curve::*;
+pub use curve25519::*;
+pub use curve448::*;
+pub use ecdh::*;
 pub use ecdsa::*;
+pub use ecies::*;
+pub use ed25519::*;
+pub use edwards::*;
+pub use elgamal::*;
+pub use glv::*;
+pub use hash_to_curve::*;
+pub use jacobian::*;
+pub use montgomery::*;
+pub use msm::*;
+pub use p256::*;
+pub use p384::*;
+pub use p521::*;
+pub use pairing::*;
+pub use pedersen::*;
 pub use point::*;
+pub use projective::*;
+pub use rfc6979::*;
+pub use scalar::*;
+pub use schnorr::*;
 pub use secp256k1::*;
+pub use signature::*;
+pub use wnaf::*;
@@ -0,0 +1,408 @@
+use crate::curve::from_hex;
+use crate::edwards::{EdwardsParams, EdwardsPoint};
+use finite_field::ff::FiniteField;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use rand::RngCore;
+use std::fmt::{self, Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Add, Neg};
+use zeroize::Zeroize;
+
+/// Parameters of a montgomery curve B*y^2 = x^3 + A*x^2 + x over F_p
+///
+/// all the numbers are given as hex strings
+pub trait MontgomeryParams: Debug + Clone + PartialEq {
+    /// name of the curve
+    const NAME: &'static str;
+
+    /// prime of the base field
+    const P: &'static str;
+
+    /// coefficient A
+    const A: &'static str;
+
+    /// coefficient B
+    const B: &'static str;
+
+    /// u coordinate of the generator point
+    const GU: &'static str;
+
+    /// v coordinate of the generator point
+    const GV: &'static str;
+
+    /// order of the generator point
+    const N: &'static str;
+
+    /// cofactor h = #E(F_p) / n
+    const H: &'static str;
+
+    /// number of bits of the scalars and u coordinates used by the ladder (255 for X25519, 448 for X448)
+    const BITS: usize;
+
+    /// return the prime of the base field
+    fn p() -> BigUint {
+        from_hex(Self::P)
+    }
+
+    /// return the number as an element of the base field
+    fn field(num: BigUint) -> FF {
+        FF::new(num, Self::p())
+    }
+
+    /// return the coefficient A
+    fn a() -> FF {
+        Self::field(from_hex(Self::A))
+    }
+
+    /// return the coefficient B
+    fn b() -> FF {
+        Self::field(from_hex(Self::B))
+    }
+
+    /// return the generator point (u, v)
+    fn generator() -> (BigUint, BigUint) {
+        (from_hex(Self::GU), from_hex(Self::GV))
+    }
+
+    /// return the order of the generator point
+    fn order() -> BigUint {
+        from_hex(Self::N)
+    }
+
+    /// return the cofactor
+    fn cofactor() -> BigUint {
+        from_hex(Self::H)
+    }
+}
+
+/// Montgomery curve which is birationally equivalent to the twisted edwards curve Edwards
+///
+/// the maps are (x, y) = (c*u/v, (u-1)/(u+1)) and (u, v) = ((1+y)/(1-y), c*u/x)
+pub trait BirationalEdwards: MontgomeryParams {
+    type Edwards: EdwardsParams;
+
+    /// the scaling factor c
+    const C: &'static str;
+}
+
+/// Point in the montgomery curve C in affine coordinates
+#[derive(Debug, Clone)]
+pub struct MontgomeryPoint<C: MontgomeryParams> {
+    pub x: Option<FF>,
+    pub y: Option<FF>,
+    pub a: FF,
+    pub b: FF,
+    _curve: PhantomData<C>,
+}
+
+impl<C: MontgomeryParams> MontgomeryPoint<C> {
+    /// create a new point, panic if it is not in the curve
+    pub fn new(x: BigUint, y: BigUint) -> Self {
+        let (a, b) = (C::a(), C::b());
+        let x = C::field(x);
+        let y = C::field(y);
+        if b.clone() * &y.pow(2) != x.pow(3) + &(a.clone() * &x.pow(2)) + &x {
+            panic!("Not in curve");
+        }
+        Self {
+            x: Some(x),
+            y: Some(y),
+            a,
+            b,
+            _curve: PhantomData,
+        }
+    }
+
+    /// return infinity point
+    pub fn new_infinity_point() -> Self {
+        Self {
+            x: None,
+            y: None,
+            a: C::a(),
+            b: C::b(),
+            _curve: PhantomData,
+        }
+    }
+
+    /// return the generator point
+    pub fn generator() -> Self {
+        let (u, v) = C::generator();
+        Self::new(u, v)
+    }
+
+    /// return true if the point is infinity
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_none() && self.y.is_none()
+    }
+
+    /// return n*P with the x-only montgomery ladder, only the u coordinate is returned
+    pub fn ladder(&self, n: &BigUint) -> BigUint {
+        match &self.x {
+            Some(x) => ladder::<C>(n, &x.num),
+            None => BigUint::zero(),
+        }
+    }
+
+    fn with_xy(&self, x: FF, y: FF) -> Self {
+        Self {
+            x: Some(x),
+            y: Some(y),
+            a: self.a.clone(),
+            b: self.b.clone(),
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<C: BirationalEdwards> MontgomeryPoint<C> {
+    /// map the point to the equivalent twisted edwards curve
+    pub fn to_edwards(&self) -> EdwardsPoint<C::Edwards> {
+        let (u, v) = match (&self.x, &self.y) {
+            (Some(u), Some(v)) => (u, v),
+            _ => return EdwardsPoint::identity(),
+        };
+        let one = C::field(BigUint::one());
+        if u.num.is_zero() {
+            // (0, 0) has order 2 and maps to (0, -1)
+            return EdwardsPoint::new(BigUint::zero(), (one.to_zero() - &one).num);
+        }
+        let c = C::field(from_hex(C::C));
+        let x = c * u / v.clone();
+        let y = (u.clone() - &one) / (u.clone() + &one);
+        EdwardsPoint::new(x.num, y.num)
+    }
+
+    /// map a point of the equivalent twisted edwards curve to the montgomery curve
+    pub fn from_edwards(p: &EdwardsPoint<C::Edwards>) -> Self {
+        if p.is_identity() {
+            return Self::new_infinity_point();
+        }
+        let (x, y) = p.xy();
+        if x.num.is_zero() {
+            // (0, -1) maps to (0, 0)
+            return Self::new(BigUint::zero(), BigUint::zero());
+        }
+        let one = C::field(BigUint::one());
+        let c = C::field(from_hex(C::C));
+        let u = (one.clone() + &C::field(y.num.clone())) / (one - &C::field(y.num));
+        let v = c * &u / C::field(x.num);
+        Self::new(u.num, v.num)
+    }
+}
+
+/// x-only montgomery ladder as in RFC 7748 section 5
+///
+/// return the u coordinate of k*P where u is the u coordinate of P
+pub fn ladder<C: MontgomeryParams>(k: &BigUint, u: &BigUint) -> BigUint {
+    let p = C::p();
+    // a24 = (A - 2) / 4
+    let a24 = (C::a() - &C::field(BigUint::from(2_u32))) / C::field(BigUint::from(4_u32));
+
+    let x1 = C::field(u.clone());
+    let mut x2 = C::field(BigUint::one());
+    let mut z2 = C::field(BigUint::zero());
+    let mut x3 = x1.clone();
+    let mut z3 = C::field(BigUint::one());
+    let mut swap = 0_u8;
+
+    for t in (0..C::BITS as u64).rev() {
+        let k_t = k.bit(t) as u8;
+        swap ^= k_t;
+        cswap(swap, &mut x2, &mut x3);
+        cswap(swap, &mut z2, &mut z3);
+        swap = k_t;
+
+        let a = x2.clone() + &z2;
+        let aa = a.pow(2);
+        let b = x2 - &z2;
+        let bb = b.pow(2);
+        let e = aa.clone() - &bb;
+        let c = x3.clone() + &z3;
+        let d = x3 - &z3;
+        let da = d * &a;
+        let cb = c * &b;
+        x3 = (da.clone() + &cb).pow(2);
+        z3 = x1.clone() * &(da - &cb).pow(2);
+        x2 = aa.clone() * &bb;
+        z2 = e.clone() * &(aa + &(a24.clone() * &e));
+    }
+    cswap(swap, &mut x2, &mut x3);
+    cswap(swap, &mut z2, &mut z3);
+
+    // z2^(p-2) is 0 when z2 = 0, so the point at infinity is returned as 0
+    x2.num * z2.num.modpow(&(&p - BigUint::from(2_u32)), &p) % &p
+}
+
+/// cswap of RFC 7748, swap a and b if swap is 1 and keep them if it is 0, without branching
+fn cswap(swap: u8, a: &mut FF, b: &mut FF) {
+    let d = (b.clone() - &*a) * &FF::new(BigUint::from(swap), a.prime.clone());
+    *a = a.clone() + &d;
+    *b = b.clone() - &d;
+}
+
+/// Diffie-Hellman function over the montgomery curve C (X25519 and X448 in RFC 7748)
+///
+/// the secret is zeroized on drop
+#[derive(Clone)]
+pub struct Xdh<C: MontgomeryParams> {
+    secret: Vec<u8>,
+    pub pub_key: Vec<u8>,
+    _curve: PhantomData<C>,
+}
+
+impl<C: MontgomeryParams> Xdh<C> {
+    /// Create a new key pair from the secret, panic if the secret has a wrong length
+    pub fn new(secret: Vec<u8>) -> Self {
+        if secret.len() != Self::key_len() {
+            panic!("secret must be {} bytes", Self::key_len());
+        }
+        let (u, _) = C::generator();
+        let pub_key = Self::xdh(&secret, &Self::encode_u(&u));
+        Self {
+            secret,
+            pub_key,
+            _curve: PhantomData,
+        }
+    }
+
+    /// Create a new key pair with a random secret
+    pub fn generate() -> Self {
+        let mut secret = vec![0_u8; Self::key_len()];
+        rand::thread_rng().fill_bytes(&mut secret);
+        Self::new(secret)
+    }
+
+    /// return the secret key
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.secret.clone()
+    }
+
+    /// compute the shared secret with the public key of the other party
+    ///
+    /// return None if the shared secret is all zero which happens for small order public keys
+    pub fn diffie_hellman(&self, their_pub_key: &[u8]) -> Option<Vec<u8>> {
+        let shared = Self::xdh(&self.secret, their_pub_key);
+        if shared.iter().all(|&b| b == 0) {
+            return None;
+        }
+        Some(shared)
+    }
+
+    /// the X25519 / X448 function: k*u with the clamped scalar k
+    pub fn xdh(k: &[u8], u: &[u8]) -> Vec<u8> {
+        let k = Self::decode_scalar(k);
+        let u = Self::decode_u(u);
+        Self::encode_u(&ladder::<C>(&k, &u))
+    }
+
+    /// decode and clamp a scalar: clear the cofactor bits, clear the bits above BITS
+    /// and set the bit BITS - 1
+    pub fn decode_scalar(k: &[u8]) -> BigUint {
+        let mut k = BigUint::from_bytes_le(k);
+        let cofactor_bits = C::cofactor().bits() - 1;
+        for i in 0..cofactor_bits {
+            k.set_bit(i, false);
+        }
+        for i in C::BITS as u64..k.bits() {
+            k.set_bit(i, false);
+        }
+        k.set_bit(C::BITS as u64 - 1, true);
+        k
+    }
+
+    /// decode a u coordinate, the bits above BITS are ignored and the result is reduced mod p
+    pub fn decode_u(u: &[u8]) -> BigUint {
+        let mut u = BigUint::from_bytes_le(u);
+        for i in C::BITS as u64..u.bits() {
+            u.set_bit(i, false);
+        }
+        u % C::p()
+    }
+
+    /// encode a u coordinate in little-endian
+    pub fn encode_u(u: &BigUint) -> Vec<u8> {
+        let mut bytes = (u % C::p()).to_bytes_le();
+        bytes.resize(Self::key_len(), 0);
+        bytes
+    }
+
+    fn key_len() -> usize {
+        C::BITS.div_ceil(8)
+    }
+}
+
+impl<C: MontgomeryParams> Debug for Xdh<C> {
+    /// the secret is not printed
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Xdh")
+            .field("pub_key", &self.pub_key)
+            .finish()
+    }
+}
+
+impl<C: MontgomeryParams> Drop for Xdh<C> {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl<C: MontgomeryParams> PartialEq for MontgomeryPoint<C> {
+    fn eq(&self, other: &MontgomeryPoint<C>) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<C: MontgomeryParams> Neg for MontgomeryPoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        match &self.y {
+            Some(y) => {
+                let y = y.to_zero() - y;
+                Self { y: Some(y), ..self }
+            }
+            None => self,
+        }
+    }
+}
+
+impl<C: MontgomeryParams> Add for MontgomeryPoint<C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl<C: MontgomeryParams> Add<&Self> for MontgomeryPoint<C> {
+    type Output = Self;
+
+    /// lambda = (y2 - y1) / (x2 - x1), or (3*x1^2 + 2*A*x1 + 1) / (2*B*y1) for doubling
+    ///
+    /// x3 = B*lambda^2 - A - x1 - x2, y3 = lambda*(x1 - x3) - y1
+    fn add(self, rhs: &Self) -> Self::Output {
+        match (&self.x, &self.y, rhs.x.as_ref(), rhs.y.as_ref()) {
+            (None, None, _, _) => rhs.clone(),
+            (_, _, None, None) => self,
+            (Some(x1), Some(y1), Some(x2), Some(y2)) => {
+                let lambda = if x1 != x2 {
+                    (y2.clone() - y1) / (x2.clone() - x1)
+                } else if y1 == y2 && !y1.num.is_zero() {
+                    let x1x1 = x1.pow(2);
+                    let ax1 = self.a.clone() * x1;
+                    let one = C::field(BigUint::one());
+                    let by1 = self.b.clone() * y1;
+                    (x1x1.clone() + &x1x1 + &x1x1 + &ax1 + &ax1 + &one) / (by1.clone() + &by1)
+                } else {
+                    return Self::new_infinity_point();
+                };
+                let x3 = self.b.clone() * &lambda.pow(2) - &self.a - x1 - x2;
+                let y3 = lambda * &(x1.clone() - &x3) - y1;
+                self.with_xy(x3, y3)
+            }
+            _ => panic!("Invalid point"),
+        }
+    }
+}
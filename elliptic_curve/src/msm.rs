@@ -0,0 +1,149 @@
+use crate::jacobian::JacobianPoint;
+use crate::point::ECCPoint;
+use crate::wnaf::{is_valid_msm_input, straus_mul, window_digit};
+use num_bigint::BigUint;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// below this number of points Straus is faster than Pippenger
+pub const STRAUS_THRESHOLD: usize = 32;
+
+/// multi scalar multiplication sum k_i*P_i
+///
+/// use Straus for small inputs and Pippenger with an automatic window width otherwise.
+/// Return None if the lengths differ, there is no point, as the curve of infinity is then
+/// unknown, or the points are not in the same curve
+pub fn msm(points: &[ECCPoint], scalars: &[BigUint]) -> Option<ECCPoint> {
+    if points.len() < STRAUS_THRESHOLD {
+        return straus_mul(points, scalars);
+    }
+    pippenger_mul(points, scalars, pippenger_window(points.len()))
+}
+
+/// window width of Pippenger for n points, about ln(n) + 2 bits
+pub fn pippenger_window(n: usize) -> usize {
+    if n < STRAUS_THRESHOLD {
+        return 3;
+    }
+    ((n as f64).ln() as usize + 2).min(16)
+}
+
+/// sum k_i*P_i with the Pippenger bucket method and windows of w bits
+///
+/// in every window the points are put in the bucket of their digit, then the buckets are
+/// summed with a running sum so sum d*B_d costs 2^(w+1) additions. The windows are
+/// computed in parallel with the parallel feature. Return None if the input is not valid,
+/// see msm, and panic if w is not in [1, 16]
+pub fn pippenger_mul(points: &[ECCPoint], scalars: &[BigUint], w: usize) -> Option<ECCPoint> {
+    if !(1..=16).contains(&w) {
+        panic!("window width must be between 1 and 16");
+    }
+    if !is_valid_msm_input(points, scalars) {
+        return None;
+    }
+    let (a, b) = (&points[0].a, &points[0].b);
+    let infinity = JacobianPoint::new_infinity_point(a.clone(), b.clone());
+    let bits = scalars.iter().map(|k| k.bits()).max().unwrap() as usize;
+
+    let window_sum = |start: usize| {
+        let mut buckets = vec![infinity.clone(); (1 << w) - 1];
+        for (p, k) in points.iter().zip(scalars) {
+            let d = window_digit(k, start, w);
+            if d != 0 {
+                buckets[d - 1] = buckets[d - 1].add_mixed(p);
+            }
+        }
+        // sum d*B_d = B_max + (B_max + B_max-1) + ... with a running sum
+        let mut running = infinity.clone();
+        let mut sum = infinity.clone();
+        for bucket in buckets.iter().rev() {
+            running = running + bucket;
+            sum = sum + &running;
+        }
+        sum
+    };
+
+    let starts: Vec<usize> = (0..bits).step_by(w).collect();
+    #[cfg(feature = "parallel")]
+    let sums: Vec<JacobianPoint> = starts.into_par_iter().map(window_sum).collect();
+    #[cfg(not(feature = "parallel"))]
+    let sums: Vec<JacobianPoint> = starts.into_iter().map(window_sum).collect();
+
+    let mut r = infinity;
+    for sum in sums.iter().rev() {
+        for _ in 0..w {
+            r = r.double();
+        }
+        r = r + sum;
+    }
+    Some(r.to_affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+    use num_bigint::RandBigInt;
+    use num_traits::Zero;
+
+    fn random_input(n: usize) -> (Vec<ECCPoint>, Vec<BigUint>) {
+        let mut rng = rand::thread_rng();
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let base = g.scalar_mul(rng.gen_biguint(256));
+        // P_i = base + i*G keeps the input cheap to build
+        let mut points = vec![base];
+        for i in 1..n {
+            let next = points[i - 1].clone() + &g;
+            points.push(next);
+        }
+        let scalars = (0..n).map(|_| rng.gen_biguint(256)).collect();
+        (points, scalars)
+    }
+
+    fn expected(points: &[ECCPoint], scalars: &[BigUint]) -> ECCPoint {
+        let infinity = ECCPoint::new_infinity_point(points[0].a.clone(), points[0].b.clone());
+        points
+            .iter()
+            .zip(scalars)
+            .fold(infinity, |acc, (p, k)| acc + &(p * k))
+    }
+
+    #[test]
+    fn test_msm() {
+        for n in [1, 5, 40] {
+            let (points, scalars) = random_input(n);
+            assert_eq!(msm(&points, &scalars), Some(expected(&points, &scalars)));
+        }
+        let (points, scalars) = random_input(40);
+        assert_eq!(msm(&points, &scalars[1..]), None);
+        assert_eq!(pippenger_mul(&points[1..], &scalars, 3), None);
+        assert_eq!(msm(&[], &[]), None);
+    }
+
+    #[test]
+    fn test_pippenger() {
+        let (mut points, mut scalars) = random_input(10);
+        // infinity, zero scalar and the same point twice
+        points.push(ECCPoint::new_infinity_point(
+            points[0].a.clone(),
+            points[0].b.clone(),
+        ));
+        scalars.push(BigUint::from(5_u32));
+        points.push(points[1].clone());
+        scalars.push(BigUint::zero());
+        points.push(points[2].clone());
+        scalars.push(scalars[2].clone());
+        let expected = expected(&points, &scalars);
+        for w in [1, 3, 7] {
+            assert_eq!(pippenger_mul(&points, &scalars, w), Some(expected.clone()));
+        }
+        assert_eq!(straus_mul(&points, &scalars), Some(expected));
+    }
+
+    #[test]
+    fn test_pippenger_window() {
+        assert_eq!(pippenger_window(10), 3);
+        assert_eq!(pippenger_window(100), 6);
+        assert_eq!(pippenger_window(1 << 20), 15);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::curve::{Curve, CurveParams};
+use crate::hash_to_curve::HashToCurveParams;
+
+/// Parameters of the NIST P-256 curve (secp256r1)
+/// https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-186.pdf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct P256Params;
+
+impl CurveParams for P256Params {
+    const NAME: &'static str = "P-256";
+    const P: &'static str = "ffffffff00000001000000000000000000000000ffffffffffffffffffffffff";
+    const A: &'static str = "ffffffff00000001000000000000000000000000fffffffffffffffffffffffc";
+    const B: &'static str = "5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b";
+    const GX: &'static str = "6b17d1f2e12c4247f8bce6e563a440f277037d812deb33a0f4a13945d898c296";
+    const GY: &'static str = "4fe342e2fe1a7f9b8ee7eb4a7c0f9e162bce33576b315ececbb6406837bf51f5";
+    const N: &'static str = "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551";
+    const H: &'static str = "1";
+}
+
+/// suite P256_XMD:SHA-256_SSWU_RO_ of RFC 9380 section 8.2
+impl HashToCurveParams for P256Params {
+    const Z: &'static str = "ffffffff00000001000000000000000000000000fffffffffffffffffffffff5";
+}
+
+/// NIST P-256 curve
+pub type P256 = Curve<P256Params>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_nist_curve;
+
+    #[test]
+    pub fn test_p256() {
+        // k*G of the NIST point multiplication vectors
+        check_nist_curve::<P256Params>(&[
+            (
+                2,
+                "7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978",
+                "7775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1",
+            ),
+            (
+                112233445566778899,
+                "339150844ec15234807fe862a86be77977dbfb3ae3d96f4c22795513aeaab82f",
+                "b1c14ddfdc8ec1b2583f51e85a5eb3a155840f2034730e9b5ada38b674336a21",
+            ),
+        ]);
+    }
+}
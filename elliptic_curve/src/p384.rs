@@ -0,0 +1,43 @@
+use crate::curve::{Curve, CurveParams};
+
+/// Parameters of the NIST P-384 curve (secp384r1)
+/// https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-186.pdf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct P384Params;
+
+impl CurveParams for P384Params {
+    const NAME: &'static str = "P-384";
+    const P: &'static str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000ffffffff";
+    const A: &'static str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffeffffffff0000000000000000fffffffc";
+    const B: &'static str = "b3312fa7e23ee7e4988e056be3f82d19181d9c6efe8141120314088f5013875ac656398d8a2ed19d2a85c8edd3ec2aef";
+    const GX: &'static str = "aa87ca22be8b05378eb1c71ef320ad746e1d3b628ba79b9859f741e082542a385502f25dbf55296c3a545e3872760ab7";
+    const GY: &'static str = "3617de4a96262c6f5d9e98bf9292dc29f8f41dbd289a147ce9da3113b5f0b8c00a60b1ce1d7e819d7a431d7c90ea0e5f";
+    const N: &'static str = "ffffffffffffffffffffffffffffffffffffffffffffffffc7634d81f4372ddf581a0db248b0a77aecec196accc52973";
+    const H: &'static str = "1";
+}
+
+/// NIST P-384 curve
+pub type P384 = Curve<P384Params>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_nist_curve;
+
+    #[test]
+    pub fn test_p384() {
+        // k*G of the NIST point multiplication vectors
+        check_nist_curve::<P384Params>(&[
+            (
+                2,
+                "8d999057ba3d2d969260045c55b97f089025959a6f434d651d207d19fb96e9e4fe0e86ebe0e64f85b96a9c75295df61",
+                "8e80f1fa5b1b3cedb7bfe8dffd6dba74b275d875bc6cc43e904e505f256ab4255ffd43e94d39e22d61501e700a940e80",
+            ),
+            (
+                112233445566778899,
+                "a499efe48839bc3abcd1c5cedbdd51904f9514db44f4686db918983b0c9dc3aee05a88b72433e9515f91a329f5f4fa60",
+                "3b7ca28ef31f809c2f1ba24aaed847d0f8b406a4b8968542de139db5828ca410e615d1182e25b91b1131e230b727d36a",
+            ),
+        ]);
+    }
+}
@@ -0,0 +1,43 @@
+use crate::curve::{Curve, CurveParams};
+
+/// Parameters of the NIST P-521 curve (secp521r1)
+/// https://nvlpubs.nist.gov/nistpubs/SpecialPublications/NIST.SP.800-186.pdf
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct P521Params;
+
+impl CurveParams for P521Params {
+    const NAME: &'static str = "P-521";
+    const P: &'static str = "1ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+    const A: &'static str = "1fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc";
+    const B: &'static str = "051953eb9618e1c9a1f929a21a0b68540eea2da725b99b315f3b8b489918ef109e156193951ec7e937b1652c0bd3bb1bf073573df883d2c34f1ef451fd46b503f00";
+    const GX: &'static str = "0c6858e06b70404e9cd9e3ecb662395b4429c648139053fb521f828af606b4d3dbaa14b5e77efe75928fe1dc127a2ffa8de3348b3c1856a429bf97e7e31c2e5bd66";
+    const GY: &'static str = "11839296a789a3bc0045c8a5fb42c7d1bd998f54449579b446817afbd17273e662c97ee72995ef42640c550b9013fad0761353c7086a272c24088be94769fd16650";
+    const N: &'static str = "1fffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffa51868783bf2f966b7fcc0148f709a5d03bb5c9b8899c47aebb6fb71e91386409";
+    const H: &'static str = "1";
+}
+
+/// NIST P-521 curve
+pub type P521 = Curve<P521Params>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::check_nist_curve;
+
+    #[test]
+    pub fn test_p521() {
+        // k*G of the NIST point multiplication vectors
+        check_nist_curve::<P521Params>(&[
+            (
+                2,
+                "433c219024277e7e682fcb288148c282747403279b1ccc06352c6e5505d769be97b3b204da6ef55507aa104a3a35c5af41cf2fa364d60fd967f43e3933ba6d783d",
+                "f4bb8cc7f86db26700a7f3eceeeed3f0b5c6b5107c4da97740ab21a29906c42dbbb3e377de9f251f6b93937fa99a3248f4eafcbe95edc0f4f71be356d661f41b02",
+            ),
+            (
+                112233445566778899,
+                "1650048fbd63e8c30b305bf36bd7643b91448ef2206e8a0ca84a140789a99b0423a0a2533ea079ca7e049843e69e5fa2c25a163819110cec1a30acbbb3a422a40d8",
+                "10c9c64a0e0db6052dbc5646687d06dece5e9e0703153efe9cb816fe025e85354d3c5f869d6db3f4c0c01b5f97919a5e72ceebe03042e5aa99112691cffc2724828",
+            ),
+        ]);
+    }
+}
@@ -0,0 +1,373 @@
+use crate::curve::{from_hex, CurveParams, CurvePoint};
+use crate::point::Point;
+use finite_field::fp12::Fp12;
+use finite_field::fp2::Fp2;
+use finite_field::fp6::Fp6;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::BigUint;
+use num_traits::One;
+use std::marker::PhantomData;
+use std::ops::{Add, Neg};
+
+/// Family of the pairing-friendly curve, it decides the loop of the optimal Ate pairing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairingFamily {
+    /// Barreto-Naehrig curves, the loop is 6x + 2 followed by two frobenius lines
+    Bn,
+    /// Barreto-Lynn-Scott curves of embedding degree 12, the loop is x
+    Bls12,
+}
+
+/// Sextic twist E' over Fp2 which is used for G2
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TwistType {
+    /// E': y^2 = x^3 + b/xi, mapped into E(Fp12) by (x, y) -> (x*w^2, y*w^3)
+    D,
+    /// E': y^2 = x^3 + b*xi, mapped into E(Fp12) by (x, y) -> (x/w^2, y/w^3)
+    M,
+}
+
+/// Parameters of a pairing-friendly curve with embedding degree 12
+///
+/// G1 is the curve of CurveParams over Fp, G2 is the twist over Fp2 = Fp[u] / (u^2 + 1)
+/// and the pairing lands in Fp12 = Fp2[w] / (w^6 - xi)
+pub trait PairingParams: CurveParams {
+    /// family of the curve
+    const FAMILY: PairingFamily;
+
+    /// absolute value of the curve parameter x
+    const X: &'static str;
+
+    /// sign of the curve parameter x
+    const X_IS_NEGATIVE: bool;
+
+    /// non-residue xi = XI[0] + XI[1]*u
+    const XI: [&'static str; 2];
+
+    /// type of the twist
+    const TWIST: TwistType;
+
+    /// x coordinate of the G2 generator as c0 + c1*u
+    const G2X: [&'static str; 2];
+
+    /// y coordinate of the G2 generator as c0 + c1*u
+    const G2Y: [&'static str; 2];
+
+    /// return the number c[0] + c[1]*u in Fp2
+    fn fp2(c: [&str; 2]) -> Fp2 {
+        Fp2::new(Self::field(from_hex(c[0])), Self::field(from_hex(c[1])))
+    }
+
+    /// return the non-residue xi
+    fn xi() -> Fp2 {
+        Self::fp2(Self::XI)
+    }
+
+    /// return the coefficient b of the twist
+    fn twist_b() -> Fp2 {
+        let b = Fp2::from_base(Self::b());
+        match Self::TWIST {
+            TwistType::D => b / Self::xi(),
+            TwistType::M => b * Self::xi(),
+        }
+    }
+
+    /// return the G2 generator point (x, y)
+    fn g2_generator() -> (Fp2, Fp2) {
+        (Self::fp2(Self::G2X), Self::fp2(Self::G2Y))
+    }
+}
+
+/// Point in G2, the twist of the curve C over Fp2, in affine coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub struct G2Point<C: PairingParams> {
+    pub x: Option<Fp2>,
+    pub y: Option<Fp2>,
+    _curve: PhantomData<C>,
+}
+
+impl<C: PairingParams> G2Point<C> {
+    /// create a new point, panic if it is not in the twist
+    pub fn new(x: Fp2, y: Fp2) -> Self {
+        if y.square() != x.square() * &x + &C::twist_b() {
+            panic!("Not in curve");
+        }
+        Self {
+            x: Some(x),
+            y: Some(y),
+            _curve: PhantomData,
+        }
+    }
+
+    /// return infinity point
+    pub fn new_infinity_point() -> Self {
+        Self {
+            x: None,
+            y: None,
+            _curve: PhantomData,
+        }
+    }
+
+    /// return the generator point
+    pub fn generator() -> Self {
+        let (x, y) = C::g2_generator();
+        Self::new(x, y)
+    }
+
+    /// return true if the point is infinity
+    pub fn is_infinity(&self) -> bool {
+        self.x.is_none() && self.y.is_none()
+    }
+
+    /// return (x, y), panic if the point is infinity
+    pub fn xy(&self) -> (Fp2, Fp2) {
+        match (&self.x, &self.y) {
+            (Some(x), Some(y)) => (x.clone(), y.clone()),
+            _ => panic!("infinity point has no coordinates"),
+        }
+    }
+
+    /// point doubling
+    pub fn double(&self) -> Self {
+        self.clone() + self
+    }
+
+    /// return n*P with double-and-add
+    pub fn scalar_mul(&self, n: &BigUint) -> Self {
+        let mut r = Self::new_infinity_point();
+        for i in (0..n.bits()).rev() {
+            r = r.double();
+            if n.bit(i) {
+                r = r + self;
+            }
+        }
+        r
+    }
+
+    /// the frobenius endomorphism x -> x^p of E(Fp12) seen through the twist
+    ///
+    /// (x, y) -> (conj(x)*gamma^2, conj(y)*gamma^3) with gamma = xi^((p-1)/6),
+    /// gamma is inverted for the M-twist
+    pub fn frobenius(&self) -> Self {
+        if self.is_infinity() {
+            return self.clone();
+        }
+        let (x, y) = self.xy();
+        let xi = C::xi();
+        let gamma = xi.pow(&((C::p() - BigUint::one()) / BigUint::from(6_u32)));
+        let gamma = match C::TWIST {
+            TwistType::D => gamma,
+            TwistType::M => gamma.inverse(),
+        };
+        let gamma2 = gamma.square();
+        let gamma3 = gamma2.clone() * &gamma;
+        Self::new(x.conjugate() * &gamma2, y.conjugate() * &gamma3)
+    }
+}
+
+impl<C: PairingParams> Neg for G2Point<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            y: self.y.map(|y| -y),
+            ..self
+        }
+    }
+}
+
+impl<C: PairingParams> Add for G2Point<C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl<C: PairingParams> Add<&Self> for G2Point<C> {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        match slope(&self, rhs) {
+            Some(lambda) => add_with_slope(&self, rhs, &lambda),
+            None if self.is_infinity() => rhs.clone(),
+            None if rhs.is_infinity() => self,
+            None => Self::new_infinity_point(),
+        }
+    }
+}
+
+/// slope of the line through T and Q (the tangent when T = Q),
+/// None if one of them is infinity or the line is vertical
+fn slope<C: PairingParams>(t: &G2Point<C>, q: &G2Point<C>) -> Option<Fp2> {
+    let (x1, y1) = (t.x.as_ref()?, t.y.as_ref()?);
+    let (x2, y2) = (q.x.as_ref()?, q.y.as_ref()?);
+    if x1 != x2 {
+        return Some((y2.clone() - y1) / (x2.clone() - x1));
+    }
+    if y1 != y2 || y1.is_zero() {
+        return None;
+    }
+    let xx = x1.square();
+    Some((xx.clone() + &xx + &xx) / (y1.clone() + y1))
+}
+
+/// x3 = lambda^2 - x1 - x2, y3 = lambda*(x1 - x3) - y1
+fn add_with_slope<C: PairingParams>(t: &G2Point<C>, q: &G2Point<C>, lambda: &Fp2) -> G2Point<C> {
+    let (x1, y1) = t.xy();
+    let (x2, _) = q.xy();
+    let x3 = lambda.square() - &x1 - &x2;
+    let y3 = lambda.clone() * &(x1 - &x3) - &y1;
+    G2Point {
+        x: Some(x3),
+        y: Some(y3),
+        _curve: PhantomData,
+    }
+}
+
+/// return T + Q and the line through T and Q evaluated at P = (xp, yp)
+///
+/// for the D-twist the line is yp - lambda*xp*w + (lambda*xt - yt)*w^3,
+/// for the M-twist it is multiplied by w^3 which lies in Fp4 and is removed by the final exponentiation,
+/// vertical lines lie in Fp6 so they are skipped too
+fn line_step<C: PairingParams>(
+    t: &G2Point<C>,
+    q: &G2Point<C>,
+    xp: &FF,
+    yp: &FF,
+) -> (Fp12, G2Point<C>) {
+    let xi = C::xi();
+    let lambda = match slope(t, q) {
+        Some(lambda) => lambda,
+        None => return (Fp12::one(xi), t.clone() + q),
+    };
+    let (xt, yt) = t.xy();
+    let zero = xi.to_zero();
+    let c = lambda.clone() * &xt - &yt;
+    let lx = -lambda.scale(xp);
+    let yp = Fp2::from_base(yp.clone());
+    let line = match C::TWIST {
+        TwistType::D => Fp12::new(
+            Fp6::new(yp, zero.clone(), zero.clone(), xi.clone()),
+            Fp6::new(lx, c, zero, xi),
+        ),
+        TwistType::M => Fp12::new(
+            Fp6::new(c, lx, zero.clone(), xi.clone()),
+            Fp6::new(zero.clone(), yp, zero, xi),
+        ),
+    };
+    (line, add_with_slope(t, q, &lambda))
+}
+
+/// Miller loop of the optimal Ate pairing
+///
+/// BN: f_{6x+2,Q}(P) * l_{[6x+2]Q,pi(Q)}(P) * l_{[6x+2]Q+pi(Q),-pi^2(Q)}(P)
+///
+/// BLS12: f_{x,Q}(P)
+pub fn miller_loop<C: PairingParams>(p: &CurvePoint<C>, q: &G2Point<C>) -> Fp12 {
+    let mut f = Fp12::one(C::xi());
+    if p.is_infinity() || q.is_infinity() {
+        return f;
+    }
+    let (xp, yp) = p.xy();
+    let x = from_hex(C::X);
+    let s = match (C::FAMILY, C::X_IS_NEGATIVE) {
+        (PairingFamily::Bn, false) => x * 6_u32 + 2_u32,
+        (PairingFamily::Bn, true) => x * 6_u32 - 2_u32,
+        (PairingFamily::Bls12, _) => x,
+    };
+
+    let mut t = q.clone();
+    for i in (0..s.bits() - 1).rev() {
+        let (line, t2) = line_step(&t, &t, &xp, &yp);
+        f = f.square() * &line;
+        t = t2;
+        if s.bit(i) {
+            let (line, t2) = line_step(&t, q, &xp, &yp);
+            f = f * &line;
+            t = t2;
+        }
+    }
+    // f_{-s,Q} = 1 / f_{s,Q} up to vertical lines, and 1/f = conj(f) after the final exponentiation
+    if C::X_IS_NEGATIVE {
+        f = f.conjugate();
+        t = -t;
+    }
+
+    if C::FAMILY == PairingFamily::Bn {
+        let q1 = q.frobenius();
+        let q2 = -q1.frobenius();
+        let (line, t2) = line_step(&t, &q1, &xp, &yp);
+        f = f * &line;
+        let (line, _) = line_step(&t2, &q2, &xp, &yp);
+        f = f * &line;
+    }
+    f
+}
+
+/// return f^((p^12 - 1) / r)
+///
+/// the easy part (p^6 - 1)(p^2 + 1) uses the conjugate and the frobenius map,
+/// the hard part (p^4 - p^2 + 1) / r is a plain exponentiation
+pub fn final_exponentiation<C: PairingParams>(f: &Fp12) -> Fp12 {
+    let f = f.conjugate() * &f.inverse();
+    let f = f.frobenius_map(2) * &f;
+
+    let p2 = C::p().pow(2);
+    let e = (&p2 * &p2 - &p2 + BigUint::one()) / C::order();
+    f.pow(&e)
+}
+
+/// optimal Ate pairing e: G1 x G2 -> GT, the subgroup of order r of Fp12
+pub fn pairing<C: PairingParams>(p: &CurvePoint<C>, q: &G2Point<C>) -> Fp12 {
+    final_exponentiation::<C>(&miller_loop(p, q))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bls12_381::Bls12_381Params;
+    use crate::bn254::Bn254Params;
+    use crate::curve::Curve;
+
+    fn check_pairing<C: PairingParams>() {
+        let p = Curve::<C>::new().g().clone();
+        let q = G2Point::<C>::generator();
+        let (a, b) = (BigUint::from(6_u32), BigUint::from(35_u32));
+
+        let e = pairing(&p, &q);
+        // non-degeneracy and e(P, Q) is in the subgroup of order r
+        assert!(!e.is_one());
+        assert!(e.pow(&C::order()).is_one());
+
+        // bilinearity: e(aP, bQ) = e(bP, aQ) = e(P, Q)^(ab)
+        let eab = pairing(&p.scalar_mul(a.clone()), &q.scalar_mul(&b));
+        assert_eq!(eab, e.pow(&(&a * &b)));
+        assert_eq!(eab, pairing(&p.scalar_mul(b.clone()), &q.scalar_mul(&a)));
+
+        // e(P, -Q) = e(P, Q)^-1
+        assert_eq!(pairing(&p, &-q.clone()) * &e, e.to_one());
+        assert!(pairing(&Curve::<C>::new().infinity(), &q).is_one());
+        assert!(pairing(&p, &G2Point::new_infinity_point()).is_one());
+    }
+
+    #[test]
+    fn test_bn254_pairing() {
+        check_pairing::<Bn254Params>();
+    }
+
+    #[test]
+    fn test_bls12_381_pairing() {
+        check_pairing::<Bls12_381Params>();
+    }
+
+    #[test]
+    fn test_g2_arithmetic() {
+        let q = G2Point::<Bn254Params>::generator();
+        let q3 = q.scalar_mul(&BigUint::from(3_u32));
+        assert_eq!(q.double() + &q, q3);
+        assert_eq!(q3.clone() + (-q.clone()), q.double());
+        assert!((q.clone() + (-q.clone())).is_infinity());
+        assert_eq!(q.clone() + G2Point::new_infinity_point(), q);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::curve::CurvePoint;
+use crate::hash_to_curve::{hash_to_curve, HashToCurveParams};
+use crate::secp256k1::Secp256k1Params;
+use num_bigint::BigUint;
+use std::iter::Sum;
+use std::ops::Add;
+
+/// Pedersen commitment C = m*G + r*H, or sum m_i*G_i + r*H for a vector
+///
+/// commitments are additively homomorphic, the sum opens to the sums of the values and
+/// of the blinding factors modulo n
+#[derive(Debug, Clone, PartialEq)]
+pub struct PedersenCommitment<C: HashToCurveParams = Secp256k1Params> {
+    point: CurvePoint<C>,
+}
+
+impl<C: HashToCurveParams> PedersenCommitment<C> {
+    /// return the committed point
+    pub fn as_point(&self) -> &CurvePoint<C> {
+        &self.point
+    }
+
+    /// encode the commitment as a compressed SEC1 point
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.point.to_bytes(true)
+    }
+
+    /// decode a commitment, return None if it is not a point of the curve
+    ///
+    /// the identity [0x00] is accepted, it is the commitment of m = 0 with r = 0 and
+    /// to_bytes of that commitment
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let point = CurvePoint::from_bytes(bytes)?;
+        Some(Self { point })
+    }
+}
+
+impl<C: HashToCurveParams> Add for PedersenCommitment<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self + &other
+    }
+}
+
+impl<C: HashToCurveParams> Add<&Self> for PedersenCommitment<C> {
+    type Output = Self;
+
+    fn add(self, other: &Self) -> Self {
+        Self {
+            point: self.point + &other.point,
+        }
+    }
+}
+
+impl<C: HashToCurveParams> Sum for PedersenCommitment<C> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        Self {
+            point: iter.map(|c| c.point).sum(),
+        }
+    }
+}
+
+/// Generators G_1, ..., G_n and H of Pedersen commitments
+///
+/// they are hash_to_curve of "G" || i and "H" with the domain tag as DST, so nobody
+/// knows a discrete log between them and commitments are binding
+#[derive(Debug, Clone)]
+pub struct PedersenGenerators<C: HashToCurveParams = Secp256k1Params> {
+    g: Vec<CurvePoint<C>>,
+    h: CurvePoint<C>,
+}
+
+impl<C: HashToCurveParams> PedersenGenerators<C> {
+    /// derive the blinding generator H and n value generators for the domain tag
+    pub fn new(domain: &[u8], n: usize) -> Self {
+        let g = (0..n as u64)
+            .map(|i| hash_to_curve::<C>(&[b"G".as_slice(), &i.to_be_bytes()].concat(), domain))
+            .collect();
+        Self {
+            g,
+            h: hash_to_curve::<C>(b"H", domain),
+        }
+    }
+
+    /// return the value generators
+    pub fn g(&self) -> &[CurvePoint<C>] {
+        &self.g
+    }
+
+    /// return the blinding generator
+    pub fn h(&self) -> &CurvePoint<C> {
+        &self.h
+    }
+
+    /// commit to m with the blinding factor r, return None if there is no value generator
+    ///
+    /// m*G and r*H are both computed in constant time
+    pub fn commit(&self, m: &BigUint, r: &BigUint) -> Option<PedersenCommitment<C>> {
+        let g = self.g.first()?;
+        Some(PedersenCommitment {
+            point: g.constant_time_mul(m) + self.h.constant_time_mul(r),
+        })
+    }
+
+    /// commit to the vector m with the blinding factor r, return None if m is longer
+    /// than the value generators
+    ///
+    /// r*H is computed in constant time but sum m_i*G_i uses a variable time multi-scalar
+    /// multiplication, so the values are not protected against side channels
+    pub fn commit_vector(&self, m: &[BigUint], r: &BigUint) -> Option<PedersenCommitment<C>> {
+        if m.len() > self.g.len() {
+            return None;
+        }
+        let values = CurvePoint::msm(&self.g[..m.len()], m)?;
+        Some(PedersenCommitment {
+            point: values + self.h.constant_time_mul(r),
+        })
+    }
+
+    /// return true if (m, r) opens the commitment, m and r must be below n so every
+    /// commitment has a single opening
+    pub fn verify(&self, commitment: &PedersenCommitment<C>, m: &BigUint, r: &BigUint) -> bool {
+        let n = C::order();
+        m < &n && r < &n && self.commit(m, r).as_ref() == Some(commitment)
+    }
+
+    /// return true if (m, r) opens the vector commitment, false if m is too long or a
+    /// scalar is not below n
+    pub fn verify_vector(
+        &self,
+        commitment: &PedersenCommitment<C>,
+        m: &[BigUint],
+        r: &BigUint,
+    ) -> bool {
+        let n = C::order();
+        m.iter().chain([r]).all(|x| x < &n) && self.commit_vector(m, r).as_ref() == Some(commitment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::{Curve, CurveParams};
+    use crate::p256::P256Params;
+    use num_bigint::RandBigInt;
+
+    const DOMAIN: &[u8] = b"PEDERSEN-TEST-V01";
+
+    fn random(n: &BigUint) -> BigUint {
+        rand::thread_rng().gen_biguint_below(n)
+    }
+
+    #[test]
+    fn test_generators() {
+        let gens = PedersenGenerators::<Secp256k1Params>::new(DOMAIN, 4);
+        let again = PedersenGenerators::<Secp256k1Params>::new(DOMAIN, 2);
+        assert_eq!(gens.g()[..2], again.g()[..]);
+        assert_eq!(gens.h(), again.h());
+        let e = Curve::<Secp256k1Params>::new();
+        for (i, p) in gens.g().iter().chain([gens.h()]).enumerate() {
+            assert!(e.validate_public_key(p));
+            assert_ne!(p, e.g());
+            for q in gens.g().iter().skip(i + 1) {
+                assert_ne!(p, q);
+            }
+        }
+        let other = PedersenGenerators::<Secp256k1Params>::new(b"OTHER-DOMAIN", 1);
+        assert_ne!(other.g()[0], gens.g()[0]);
+        assert_ne!(other.h(), gens.h());
+    }
+
+    #[test]
+    fn test_commit() {
+        let n = Secp256k1Params::order();
+        let gens = PedersenGenerators::<Secp256k1Params>::new(DOMAIN, 1);
+        let (m, r) = (BigUint::from(42_u32), random(&n));
+        let c = gens.commit(&m, &r).unwrap();
+        assert!(gens.verify(&c, &m, &r));
+        assert!(!gens.verify(&c, &BigUint::from(43_u32), &r));
+        assert!(!gens.verify(&c, &m, &(&r + 1_u32)));
+        assert_ne!(gens.commit(&m, &random(&n)), Some(c.clone()));
+        assert_eq!(
+            gens.commit_vector(std::slice::from_ref(&m), &r),
+            Some(c.clone())
+        );
+        assert_eq!(
+            PedersenCommitment::from_bytes(&c.to_bytes()),
+            Some(c.clone())
+        );
+
+        // m + n and r + n commit to the same point but are not canonical openings
+        assert!(!gens.verify(&c, &(&m + &n), &r));
+        assert!(!gens.verify(&c, &m, &(&r + &n)));
+        assert!(!gens.verify_vector(&c, &[&m + &n], &r));
+        assert!(!gens.verify_vector(&c, std::slice::from_ref(&m), &(&r + &n)));
+
+        let zero = BigUint::from(0_u32);
+        let identity = gens.commit(&zero, &zero).unwrap();
+        assert!(identity.as_point().is_infinity());
+        assert_eq!(identity.to_bytes(), vec![0]);
+        assert_eq!(PedersenCommitment::from_bytes(&[0]), Some(identity));
+
+        let empty = PedersenGenerators::<Secp256k1Params>::new(DOMAIN, 0);
+        assert_eq!(empty.commit(&m, &r), None);
+        assert!(!empty.verify(&c, &m, &r));
+    }
+
+    #[test]
+    fn test_homomorphism() {
+        let n = P256Params::order();
+        let gens = PedersenGenerators::<P256Params>::new(DOMAIN, 1);
+        let (m1, r1) = (random(&n), random(&n));
+        let (m2, r2) = (random(&n), random(&n));
+        let c = gens.commit(&m1, &r1).unwrap() + gens.commit(&m2, &r2).unwrap();
+        assert!(gens.verify(&c, &((&m1 + &m2) % &n), &((&r1 + &r2) % &n)));
+        let sum: PedersenCommitment<P256Params> = (1..=3_u32)
+            .map(|i| gens.commit(&BigUint::from(i), &BigUint::from(i)).unwrap())
+            .sum();
+        assert!(gens.verify(&sum, &BigUint::from(6_u32), &BigUint::from(6_u32)));
+    }
+
+    #[test]
+    fn test_commit_vector() {
+        let n = Secp256k1Params::order();
+        let gens = PedersenGenerators::<Secp256k1Params>::new(DOMAIN, 8);
+        let m: Vec<BigUint> = (0..5).map(|_| random(&n)).collect();
+        let r = random(&n);
+        let c = gens.commit_vector(&m, &r).unwrap();
+        let expected: CurvePoint<_> = gens
+            .g()
+            .iter()
+            .zip(&m)
+            .map(|(g, m)| g * m)
+            .sum::<CurvePoint<_>>()
+            + gens.h() * &r;
+        assert_eq!(*c.as_point(), expected);
+        assert!(gens.verify_vector(&c, &m, &r));
+        let mut wrong = m.clone();
+        wrong.swap(0, 1);
+        assert!(!gens.verify_vector(&c, &wrong, &r));
+        assert!(!gens.verify_vector(&c, &vec![BigUint::from(0_u32); 9], &r));
+        assert_eq!(gens.commit_vector(&vec![BigUint::from(0_u32); 9], &r), None);
+
+        let m2: Vec<BigUint> = (0..5).map(|_| random(&n)).collect();
+        let r2 = random(&n);
+        let sum_m: Vec<BigUint> = m.iter().zip(&m2).map(|(a, b)| (a + b) % &n).collect();
+        let c = c + gens.commit_vector(&m2, &r2).unwrap();
+        assert!(gens.verify_vector(&c, &sum_m, &((r + r2) % &n)));
+    }
+}
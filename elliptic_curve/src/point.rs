@@ -1,8 +1,10 @@
+use crate::wnaf::{wnaf_mul, WNAF_WINDOW};
 use finite_field::ff::FiniteField;
+use finite_field::helper::tonelli_shanks;
 use finite_field::prime_field::PrimeField as FF;
 use num_bigint::BigUint;
-use num_traits::{One, Zero};
-use std::ops::Add;
+use num_traits::Zero;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
 
 pub type PointData = (Option<FF>, Option<FF>, FF, FF);
 
@@ -23,6 +25,9 @@ pub trait Point:
     fn xy(&self) -> (FF, FF);
 }
 
+/// Untyped point of the curve given by its a and b
+///
+/// it has no Sum as the curve of an empty sum is unknown, CurvePoint<C> sums to infinity
 #[derive(Debug, Clone)]
 pub struct ECCPoint {
     pub x: Option<FF>,
@@ -88,18 +93,54 @@ impl ECCPoint {
     /// input: n
     ///
     /// output: n*P which P is point in elliptic curve
-    pub fn scalar_mul(&self, mut n: BigUint) -> Self {
-        let mut q = self.clone();
+    ///
+    /// use w-NAF with the default window, see wnaf_mul to choose the window width
+    pub fn scalar_mul(&self, n: BigUint) -> Self {
+        self * &n
+    }
+
+    /// point doubling 2P
+    ///
+    /// lamda = (3*x1^2 + a) / (2*y1), x3 = lamda^2 - 2*x1, y3 = lamda*(x1 - x3) - y1
+    pub fn double(&self) -> Self {
+        let (x1, y1) = match (&self.x, &self.y) {
+            (Some(x1), Some(y1)) if !y1.num.is_zero() => (x1, y1),
+            _ => return Self::new_infinity_point(self.a.clone(), self.b.clone()),
+        };
+        let prime = &self.a.prime;
+        let big3 = FF::new(BigUint::from(3_u32), prime.clone());
+        let big2 = FF::new(BigUint::from(2_u32), prime.clone());
+
+        let doub_y1 = big2 * y1;
+        let sqrt_x1 = x1.pow(2);
 
-        let mut r = ECCPoint::new(&(None, None, q.a.clone(), q.b.clone()));
-        while n > BigUint::zero() {
-            if n.clone() % BigUint::from(2_u32) == BigUint::one() {
-                r = r + q.clone();
+        let a = &self.a;
+        let lamda = (big3 * sqrt_x1 + a) / (doub_y1);
+
+        let x3 = lamda.pow(2) - x1 - x1;
+        let y3 = lamda * (x1.clone() - &x3) - y1;
+
+        Self {
+            x: Some(x3),
+            y: Some(y3),
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+
+    /// return true if the point is infinity or (x, y) with x, y < p and y^2 = x^3 + ax + b
+    pub fn is_on_curve(&self) -> bool {
+        match (&self.x, &self.y) {
+            (None, None) => true,
+            (Some(x), Some(y)) => {
+                let prime = &self.a.prime;
+                [x, y, &self.b]
+                    .iter()
+                    .all(|c| &c.prime == prime && &c.num < prime)
+                    && y.pow(2) == x.pow(3) + &(self.a.clone() * x) + &self.b
             }
-            q = q.clone() + q.clone();
-            n = &n / BigUint::from(2_u32);
+            _ => false,
         }
-        r
     }
 
     /// return infinity point
@@ -111,6 +152,149 @@ impl ECCPoint {
             b,
         }
     }
+
+    /// lift x to the point (x, y) such that y^2 = x^3 + ax + b and y is odd if odd is true
+    ///
+    /// return None if x^3 + ax + b is not a square
+    pub fn lift_x(x: &FF, odd: bool, a: &FF, b: &FF) -> Option<Self> {
+        let prime = &a.prime;
+        let y2 = x.pow(3) + &(a.clone() * x) + b;
+        let y = tonelli_shanks(y2.num, prime.clone())?;
+        let y = if y.bit(0) != odd {
+            (prime - y) % prime
+        } else {
+            y
+        };
+        if y.bit(0) != odd {
+            // y = 0 has no odd root
+            return None;
+        }
+        Some(Self {
+            x: Some(x.clone()),
+            y: Some(FF::new(y, prime.clone())),
+            a: a.clone(),
+            b: b.clone(),
+        })
+    }
+
+    /// encode the point as in SEC1 section 2.3.3
+    ///
+    /// infinity is 0x00, compressed is 0x02 or 0x03 (parity of y) || x,
+    /// uncompressed is 0x04 || x || y
+    pub fn to_bytes(&self, compressed: bool) -> Vec<u8> {
+        let (x, y) = match (&self.x, &self.y) {
+            (Some(x), Some(y)) => (x, y),
+            _ => return vec![0x00],
+        };
+        let len = field_len(&self.a.prime);
+        let mut bytes = Vec::with_capacity(1 + 2 * len);
+        if compressed {
+            bytes.push(if y.num.bit(0) { 0x03 } else { 0x02 });
+            bytes.extend(to_fixed_bytes(&x.num, len));
+        } else {
+            bytes.push(0x04);
+            bytes.extend(to_fixed_bytes(&x.num, len));
+            bytes.extend(to_fixed_bytes(&y.num, len));
+        }
+        bytes
+    }
+
+    /// decode a point encoded by to_bytes as in SEC1 section 2.3.4
+    ///
+    /// return None if the encoding is not valid or the point is not in the curve y^2 = x^3 + ax + b
+    pub fn from_bytes(bytes: &[u8], a: &FF, b: &FF) -> Option<Self> {
+        let prime = &a.prime;
+        let len = field_len(prime);
+        let coordinate = |bytes: &[u8]| {
+            let n = BigUint::from_bytes_be(bytes);
+            (&n < prime).then(|| FF::new(n, prime.clone()))
+        };
+        match (bytes.first()?, bytes.len()) {
+            (0x00, 1) => Some(Self::new_infinity_point(a.clone(), b.clone())),
+            (0x02 | 0x03, n) if n == 1 + len => {
+                let x = coordinate(&bytes[1..])?;
+                Self::lift_x(&x, bytes[0] == 0x03, a, b)
+            }
+            (0x04, n) if n == 1 + 2 * len => {
+                let x = coordinate(&bytes[1..1 + len])?;
+                let y = coordinate(&bytes[1 + len..])?;
+                if y.pow(2) != x.pow(3) + &(a.clone() * &x) + b {
+                    return None;
+                }
+                Some(Self {
+                    x: Some(x),
+                    y: Some(y),
+                    a: a.clone(),
+                    b: b.clone(),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// number of bytes of a field element
+fn field_len(prime: &BigUint) -> usize {
+    (prime.bits() as usize).div_ceil(8)
+}
+
+/// big-endian bytes of n left padded with zeros to len bytes
+pub(crate) fn to_fixed_bytes(n: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = n.to_bytes_be();
+    let mut out = vec![0_u8; len - bytes.len()];
+    out.extend(bytes);
+    out
+}
+
+/// return n as an element of the field with the given prime
+pub(crate) fn small(n: u32, prime: &BigUint) -> FF {
+    FF::new(BigUint::from(n), prime.clone())
+}
+
+/// Point in (X : Y : Z) coordinates, shared by the projective and jacobian points
+pub(crate) trait ZCoordinates {
+    /// return Z, it is zero for the point at infinity
+    fn z(&self) -> &FF;
+
+    /// return the affine point at infinity of the same curve
+    fn affine_infinity(&self) -> ECCPoint;
+
+    /// return the affine point given Z^-1
+    fn to_affine_with_inverse(&self, z_inv: &FF) -> ECCPoint;
+}
+
+/// convert a list of points to affine coordinates with a single field inversion
+///
+/// use Montgomery's trick: invert the product of all Z and recover each Z^-1 from it
+pub(crate) fn batch_normalize<P: ZCoordinates>(points: &[P]) -> Vec<ECCPoint> {
+    let Some(first) = points.first() else {
+        return vec![];
+    };
+    let one = FF::new(BigUint::from(1_u32), first.z().prime.clone());
+
+    // prefix[i] = z_0 * z_1 * ... * z_{i-1}, the infinity points are skipped
+    let mut prefix = Vec::with_capacity(points.len());
+    let mut acc = one;
+    for p in points {
+        prefix.push(acc.clone());
+        if !p.z().num.is_zero() {
+            acc = acc * p.z();
+        }
+    }
+
+    let mut acc_inv = acc.inverse();
+    let mut result = Vec::with_capacity(points.len());
+    for (p, prefix) in points.iter().zip(prefix).rev() {
+        if p.z().num.is_zero() {
+            result.push(p.affine_infinity());
+            continue;
+        }
+        let z_inv = acc_inv.clone() * &prefix;
+        acc_inv = acc_inv * p.z();
+        result.push(p.to_affine_with_inverse(&z_inv));
+    }
+    result.reverse();
+    result
 }
 
 impl PartialEq for ECCPoint {
@@ -127,7 +311,7 @@ impl Add for ECCPoint {
     }
 }
 
-impl<'a> Add<&'a Self> for ECCPoint {
+impl Add<&Self> for ECCPoint {
     type Output = Self;
 
     fn add(self, rhs: &Self) -> Self::Output {
@@ -144,27 +328,7 @@ impl<'a> Add<&'a Self> for ECCPoint {
                 a: self.a,
                 b: self.b,
             },
-            (Some(x1), Some(y1), Some(_), Some(_)) if &self == rhs => {
-                let prime = &self.a.prime;
-                let big3 = FF::new(BigUint::from(3_u32), prime.clone());
-                let big2 = FF::new(BigUint::from(2_u32), prime.clone());
-
-                let doub_y1 = big2 * y1;
-                let sqrt_x1 = x1.pow(2);
-
-                let a = &self.a;
-                let lamda = (big3 * sqrt_x1 + a) / (doub_y1);
-
-                let x3 = lamda.pow(2) - x1 - x1;
-                let y3 = lamda * (x1.clone() - &x3) - y1;
-
-                Self {
-                    x: Some(x3),
-                    y: Some(y3),
-                    a: self.a.clone(),
-                    b: self.b.clone(),
-                }
-            }
+            (Some(_), Some(_), Some(_), Some(_)) if &self == rhs => self.double(),
             (Some(x1), Some(y1), Some(x2), Some(y2)) => {
                 let t1 = y2.clone() - y1;
                 let t2 = x2.clone() - x1;
@@ -184,6 +348,85 @@ impl<'a> Add<&'a Self> for ECCPoint {
     }
 }
 
+impl AddAssign for ECCPoint {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = self.clone() + &rhs;
+    }
+}
+
+impl AddAssign<&Self> for ECCPoint {
+    fn add_assign(&mut self, rhs: &Self) {
+        *self = self.clone() + rhs;
+    }
+}
+
+impl Neg for ECCPoint {
+    type Output = Self;
+
+    /// -(x, y) = (x, -y)
+    fn neg(self) -> Self::Output {
+        -&self
+    }
+}
+
+impl Neg for &ECCPoint {
+    type Output = ECCPoint;
+
+    fn neg(self) -> Self::Output {
+        ECCPoint {
+            x: self.x.clone(),
+            y: self.y.as_ref().map(|y| y.to_zero() - y),
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl Sub for ECCPoint {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self - &rhs
+    }
+}
+
+impl Sub<&Self> for ECCPoint {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl SubAssign for ECCPoint {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = self.clone() - &rhs;
+    }
+}
+
+impl SubAssign<&Self> for ECCPoint {
+    fn sub_assign(&mut self, rhs: &Self) {
+        *self = self.clone() - rhs;
+    }
+}
+
+impl Mul<&BigUint> for &ECCPoint {
+    type Output = ECCPoint;
+
+    /// n*P with w-NAF in jacobian coordinates
+    fn mul(self, n: &BigUint) -> Self::Output {
+        wnaf_mul(self, n, WNAF_WINDOW)
+    }
+}
+
+impl Mul<&BigUint> for ECCPoint {
+    type Output = ECCPoint;
+
+    fn mul(self, n: &BigUint) -> Self::Output {
+        &self * n
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -325,4 +568,106 @@ mod tests {
 
         assert_eq!(pp.scalar_mul(n), rs);
     }
+
+    #[test]
+    fn test_group_operators() {
+        let p = BigUint::from_str_radix(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap();
+        let a = FF::new(BigUint::zero(), p.clone());
+        let b = FF::new(BigUint::from_u32(7).unwrap(), p.clone());
+        let x1 = BigUint::from_str(
+            "73286588022960150853141494243436869925915710116679081821764683029732695813278",
+        )
+        .unwrap();
+        let y1 = BigUint::from_str(
+            "24078932672756686264184803023496970251662289758831078626496324175980264267340",
+        )
+        .unwrap();
+        let g = ECCPoint::new(&(
+            Some(FF::new(x1, p.clone())),
+            Some(FF::new(y1, p)),
+            a.clone(),
+            b.clone(),
+        ));
+        let inf = ECCPoint::new_infinity_point(a, b);
+
+        let g2 = g.double();
+        assert_eq!(g2, g.clone() + &g);
+        assert_eq!(&g * &BigUint::from(2_u32), g2);
+        assert_eq!(
+            g.clone() * &BigUint::from(5_u32),
+            g.scalar_mul(5_u32.into())
+        );
+        assert_eq!(inf.double(), inf);
+
+        assert_eq!(g2.clone() - &g, g);
+        assert_eq!(g.clone() - g.clone(), inf);
+        assert_eq!(-(-g.clone()), g);
+        assert_eq!(-&inf, inf);
+        assert_eq!(g.clone() + -&g, inf);
+
+        let mut acc = g.clone();
+        acc += &g;
+        acc += g.clone();
+        assert_eq!(acc, &g * &BigUint::from(3_u32));
+        acc -= &g;
+        acc -= g.clone();
+        assert_eq!(acc, g);
+    }
+
+    #[test]
+    fn test_sec1_encoding() {
+        let p = BigUint::from_str_radix(
+            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap();
+        let a = FF::new(BigUint::zero(), p.clone());
+        let b = FF::new(BigUint::from_u32(7).unwrap(), p.clone());
+        let gx = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let gy = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+        let g = ECCPoint::new(&(
+            Some(FF::new(BigUint::from_str_radix(gx, 16).unwrap(), p.clone())),
+            Some(FF::new(BigUint::from_str_radix(gy, 16).unwrap(), p.clone())),
+            a.clone(),
+            b.clone(),
+        ));
+
+        let hex =
+            |bytes: Vec<u8>| -> String { bytes.iter().map(|b| format!("{:02x}", b)).collect() };
+        assert_eq!(hex(g.to_bytes(true)), format!("02{}", gx));
+        assert_eq!(hex(g.to_bytes(false)), format!("04{}{}", gx, gy));
+
+        for n in [1_u32, 2, 3, 7, 1000] {
+            let q = g.scalar_mul(BigUint::from(n));
+            for compressed in [true, false] {
+                let bytes = q.to_bytes(compressed);
+                assert_eq!(ECCPoint::from_bytes(&bytes, &a, &b), Some(q.clone()));
+            }
+        }
+
+        let inf = ECCPoint::new_infinity_point(a.clone(), b.clone());
+        assert_eq!(inf.to_bytes(true), vec![0x00]);
+        assert_eq!(ECCPoint::from_bytes(&[0x00], &a, &b), Some(inf));
+
+        // wrong prefix, wrong length, not in the curve and x >= p
+        let mut bytes = g.to_bytes(false);
+        bytes[0] = 0x06;
+        assert_eq!(ECCPoint::from_bytes(&bytes, &a, &b), None);
+        assert_eq!(ECCPoint::from_bytes(&g.to_bytes(true)[..32], &a, &b), None);
+        let mut bytes = g.to_bytes(false);
+        bytes[64] ^= 1;
+        assert_eq!(ECCPoint::from_bytes(&bytes, &a, &b), None);
+        let mut bytes = vec![0x02];
+        bytes.extend(p.to_bytes_be());
+        assert_eq!(ECCPoint::from_bytes(&bytes, &a, &b), None);
+        assert_eq!(ECCPoint::from_bytes(&[], &a, &b), None);
+        // x = 5 gives 5^3 + 7 = 132 which is not a square modulo p
+        let mut bytes = vec![0x03; 33];
+        bytes[1..].copy_from_slice(&to_fixed_bytes(&BigUint::from(5_u32), 32));
+        assert_eq!(ECCPoint::from_bytes(&bytes, &a, &b), None);
+    }
 }
@@ -0,0 +1,402 @@
+use crate::point::{batch_normalize, small, ECCPoint, ZCoordinates};
+use finite_field::ff::FiniteField;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::ops::Add;
+
+/// Point in homogeneous projective coordinates (X : Y : Z) which represents the affine point (X/Z, Y/Z)
+///
+/// The point at infinity is (0 : 1 : 0)
+///
+/// formulas: https://www.hyperelliptic.org/EFD/g1p/auto-shortw-projective.html
+#[derive(Debug, Clone)]
+pub struct ProjectivePoint {
+    pub x: FF,
+    pub y: FF,
+    pub z: FF,
+    pub a: FF,
+    pub b: FF,
+}
+
+impl ProjectivePoint {
+    /// return infinity point
+    pub fn new_infinity_point(a: FF, b: FF) -> Self {
+        let prime = a.prime.clone();
+        Self {
+            x: FF::zero(prime.clone()),
+            y: FF::new(BigUint::one(), prime.clone()),
+            z: FF::zero(prime),
+            a,
+            b,
+        }
+    }
+
+    /// convert an affine point to projective coordinates with Z = 1
+    pub fn from_affine(p: &ECCPoint) -> Self {
+        if p.is_infinity() {
+            return Self::new_infinity_point(p.a.clone(), p.b.clone());
+        }
+        Self {
+            x: p.x.clone().unwrap(),
+            y: p.y.clone().unwrap(),
+            z: FF::new(BigUint::one(), p.a.prime.clone()),
+            a: p.a.clone(),
+            b: p.b.clone(),
+        }
+    }
+
+    /// convert back to affine coordinates, this costs one field inversion
+    pub fn to_affine(&self) -> ECCPoint {
+        if self.is_infinity() {
+            return ECCPoint::new_infinity_point(self.a.clone(), self.b.clone());
+        }
+        let z_inv = self.z.inverse();
+        self.to_affine_with_inverse(&z_inv)
+    }
+
+    /// convert a list of points to affine coordinates with a single field inversion
+    pub fn batch_normalize(points: &[Self]) -> Vec<ECCPoint> {
+        batch_normalize(points)
+    }
+
+    /// return true if the point is infinity
+    pub fn is_infinity(&self) -> bool {
+        self.z.num.is_zero()
+    }
+
+    /// point doubling
+    ///
+    /// use dbl-2007-bl, the a*Z^2 term is skipped when a = 0
+    pub fn double(&self) -> Self {
+        if self.is_infinity() || self.y.num.is_zero() {
+            return Self::new_infinity_point(self.a.clone(), self.b.clone());
+        }
+
+        let xx = self.x.pow(2);
+        let w = if self.a.num.is_zero() {
+            xx.clone() + &xx + &xx
+        } else {
+            let zz = self.z.pow(2);
+            self.a.clone() * &zz + &xx + &xx + &xx
+        };
+        let yz = self.y.clone() * &self.z;
+        let s = yz.clone() + &yz;
+        let ss = s.pow(2);
+        let sss = s.clone() * &ss;
+        let r = self.y.clone() * &s;
+        let rr = r.pow(2);
+        let b = (self.x.clone() + &r).pow(2) - &xx - &rr;
+        let h = w.pow(2) - &b - &b;
+        let x3 = h.clone() * &s;
+        let y3 = w * &(b - &h) - &rr - &rr;
+
+        Self {
+            x: x3,
+            y: y3,
+            z: sss,
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+
+    /// mixed addition of a projective point and an affine point (Z2 = 1)
+    ///
+    /// use madd-1998-cmo
+    pub fn add_mixed(&self, rhs: &ECCPoint) -> Self {
+        if self.a != rhs.a || self.b != rhs.b {
+            panic!("not in the same curve")
+        }
+        if rhs.is_infinity() {
+            return self.clone();
+        }
+        if self.is_infinity() {
+            return Self::from_affine(rhs);
+        }
+
+        let x2 = rhs.x.as_ref().unwrap();
+        let y2 = rhs.y.as_ref().unwrap();
+
+        let u = y2.clone() * &self.z - &self.y;
+        let v = x2.clone() * &self.z - &self.x;
+
+        if v.num.is_zero() {
+            if u.num.is_zero() {
+                return self.double();
+            }
+            return Self::new_infinity_point(self.a.clone(), self.b.clone());
+        }
+
+        let uu = u.pow(2);
+        let vv = v.pow(2);
+        let vvv = v.clone() * &vv;
+        let r = vv * &self.x;
+        let a = uu * &self.z - &vvv - &r - &r;
+        let x3 = v * &a;
+        let y3 = u * &(r - &a) - &(vvv.clone() * &self.y);
+        let z3 = vvv * &self.z;
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl ProjectivePoint {
+    /// complete addition, it also works for doubling and the infinity point without branching
+    ///
+    /// https://eprint.iacr.org/2015/1060.pdf algorithm 1, only valid on curves of odd order
+    pub fn add_complete(&self, rhs: &Self) -> Self {
+        let (x1, y1, z1) = (&self.x, &self.y, &self.z);
+        let (x2, y2, z2) = (&rhs.x, &rhs.y, &rhs.z);
+        let a = &self.a;
+        let b3 = small(3, &a.prime) * &self.b;
+
+        let t0 = x1.clone() * x2;
+        let t1 = y1.clone() * y2;
+        let t2 = z1.clone() * z2;
+        let t3 = (x1.clone() + y1) * &(x2.clone() + y2) - &(t0.clone() + &t1);
+        let t4 = (x1.clone() + z1) * &(x2.clone() + z2) - &(t0.clone() + &t2);
+        let t5 = (y1.clone() + z1) * &(y2.clone() + z2) - &(t1.clone() + &t2);
+        let z3 = a.clone() * &t4 + &(b3.clone() * &t2);
+        let x3 = t1.clone() - &z3;
+        let z3 = t1.clone() + &z3;
+        let y3 = x3.clone() * &z3;
+        let at2 = a.clone() * &t2;
+        let t1 = t0.clone() + &t0 + &t0 + &at2;
+        let t4 = b3 * &t4 + &(a.clone() * &(t0.clone() - &at2));
+        let y3 = y3 + &(t1.clone() * &t4);
+        let x3 = t3.clone() * &x3 - &(t5.clone() * &t4);
+        let z3 = t5 * &z3 + &(t3 * &t1);
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+
+    /// swap the two points if choice is 1 and keep them if choice is 0, without branching
+    pub fn conditional_swap(p: &mut Self, q: &mut Self, choice: u8) {
+        let choice = FF::new(BigUint::from(choice), p.a.prime.clone());
+        for (a, b) in [
+            (&mut p.x, &mut q.x),
+            (&mut p.y, &mut q.y),
+            (&mut p.z, &mut q.z),
+        ] {
+            let d = (b.clone() - &*a) * &choice;
+            *a = a.clone() + &d;
+            *b = b.clone() - &d;
+        }
+    }
+}
+
+/// k*P with a Montgomery ladder over complete projective formulas
+///
+/// the ladder always runs bits iterations with one addition and one doubling each, and the
+/// bits of k only select a conditional swap, so the sequence of operations does not depend on
+/// k. Use it for secret scalars, the BigUint arithmetic underneath is not constant time itself.
+///
+/// the complete formulas need a curve of odd order
+pub fn constant_time_mul(p: &ECCPoint, k: &BigUint, bits: u64) -> ECCPoint {
+    let mut r0 = ProjectivePoint::new_infinity_point(p.a.clone(), p.b.clone());
+    let mut r1 = ProjectivePoint::from_affine(p);
+    for i in (0..bits.max(k.bits())).rev() {
+        let bit = k.bit(i) as u8;
+        ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+        r1 = r0.add_complete(&r1);
+        r0 = r0.add_complete(&r0);
+        ProjectivePoint::conditional_swap(&mut r0, &mut r1, bit);
+    }
+    r0.to_affine()
+}
+
+impl ZCoordinates for ProjectivePoint {
+    fn z(&self) -> &FF {
+        &self.z
+    }
+
+    fn affine_infinity(&self) -> ECCPoint {
+        ECCPoint::new_infinity_point(self.a.clone(), self.b.clone())
+    }
+
+    fn to_affine_with_inverse(&self, z_inv: &FF) -> ECCPoint {
+        ECCPoint {
+            x: Some(self.x.clone() * z_inv),
+            y: Some(self.y.clone() * z_inv),
+            a: self.a.clone(),
+            b: self.b.clone(),
+        }
+    }
+}
+
+impl PartialEq for ProjectivePoint {
+    /// (X1 : Y1 : Z1) = (X2 : Y2 : Z2) iff X1*Z2 = X2*Z1 and Y1*Z2 = Y2*Z1
+    fn eq(&self, other: &ProjectivePoint) -> bool {
+        if self.a != other.a || self.b != other.b {
+            return false;
+        }
+        match (self.is_infinity(), other.is_infinity()) {
+            (true, true) => true,
+            (false, false) => {
+                self.x.clone() * &other.z == other.x.clone() * &self.z
+                    && self.y.clone() * &other.z == other.y.clone() * &self.z
+            }
+            _ => false,
+        }
+    }
+}
+
+impl Add for ProjectivePoint {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl Add<&Self> for ProjectivePoint {
+    type Output = Self;
+
+    /// use add-1998-cmo-2
+    fn add(self, rhs: &Self) -> Self::Output {
+        if self.a != rhs.a || self.b != rhs.b {
+            panic!("not in the same curve")
+        }
+        if self.is_infinity() {
+            return rhs.clone();
+        }
+        if rhs.is_infinity() {
+            return self;
+        }
+
+        let y1z2 = self.y.clone() * &rhs.z;
+        let x1z2 = self.x.clone() * &rhs.z;
+        let z1z2 = self.z.clone() * &rhs.z;
+        let u = rhs.y.clone() * &self.z - &y1z2;
+        let v = rhs.x.clone() * &self.z - &x1z2;
+
+        if v.num.is_zero() {
+            if u.num.is_zero() {
+                return self.double();
+            }
+            return Self::new_infinity_point(self.a, self.b);
+        }
+
+        let uu = u.pow(2);
+        let vv = v.pow(2);
+        let vvv = v.clone() * &vv;
+        let r = vv * &x1z2;
+        let a = uu * &z1z2 - &vvv - &r - &r;
+        let x3 = v * &a;
+        let y3 = u * &(r - &a) - &(vvv.clone() * &y1z2);
+        let z3 = vvv * &z1z2;
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            a: self.a,
+            b: self.b,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+    use crate::test_utils::small_curve_point;
+
+    #[test]
+    fn test_double() {
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let pg = ProjectivePoint::from_affine(&g);
+        assert_eq!(pg.double().to_affine(), g.clone() + &g);
+
+        let q = small_curve_point();
+        let pq = ProjectivePoint::from_affine(&q);
+        assert_eq!(pq.double().to_affine(), q.clone() + &q);
+        assert_eq!(
+            pq.double().double().to_affine(),
+            q.scalar_mul(BigUint::from(4_u32))
+        );
+    }
+
+    #[test]
+    fn test_addition() {
+        for g in [Secp256k1::new().g().as_ecc().clone(), small_curve_point()] {
+            let g2 = g.clone() + &g;
+            let g3 = g2.clone() + &g;
+            let pg = ProjectivePoint::from_affine(&g);
+            let pg2 = pg.double();
+
+            assert_eq!((pg2.clone() + &pg).to_affine(), g3);
+            assert_eq!(pg2.add_mixed(&g).to_affine(), g3);
+            assert_eq!(pg.add_mixed(&g), pg2);
+            assert_eq!(pg.clone() + &pg, pg2);
+
+            let inf = ProjectivePoint::new_infinity_point(g.a.clone(), g.b.clone());
+            assert_eq!(inf.clone() + &pg, pg);
+            assert_eq!(pg.clone() + &inf, pg);
+            assert_eq!(inf.add_mixed(&g), pg);
+            assert!(pg.add_mixed(&-&g).is_infinity());
+        }
+    }
+
+    #[test]
+    fn test_batch_normalize() {
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let pg = ProjectivePoint::from_affine(&g);
+        let inf = ProjectivePoint::new_infinity_point(g.a.clone(), g.b.clone());
+        let points = vec![pg.double(), inf, pg.double().add_mixed(&g), pg.clone()];
+        let affine = ProjectivePoint::batch_normalize(&points);
+        let expected: Vec<ECCPoint> = points.iter().map(|p| p.to_affine()).collect();
+        assert_eq!(affine, expected);
+        assert!(affine[1].is_infinity());
+        assert!(ProjectivePoint::batch_normalize(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_add_complete() {
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let pg = ProjectivePoint::from_affine(&g);
+        let pg2 = pg.double();
+        let inf = ProjectivePoint::new_infinity_point(g.a.clone(), g.b.clone());
+        let neg = ProjectivePoint::from_affine(&-&g);
+
+        assert_eq!(pg.add_complete(&pg2), pg2.clone() + &pg);
+        assert_eq!(pg.add_complete(&pg), pg2);
+        assert_eq!(inf.add_complete(&pg), pg);
+        assert_eq!(pg.add_complete(&inf), pg);
+        assert!(inf.add_complete(&inf).is_infinity());
+        assert!(pg.add_complete(&neg).is_infinity());
+
+        let (mut p, mut q) = (pg.clone(), pg2.clone());
+        ProjectivePoint::conditional_swap(&mut p, &mut q, 0);
+        assert_eq!((&p, &q), (&pg, &pg2));
+        ProjectivePoint::conditional_swap(&mut p, &mut q, 1);
+        assert_eq!((&p, &q), (&pg2, &pg));
+    }
+
+    #[test]
+    fn test_constant_time_mul() {
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let n = Secp256k1::new().n().clone();
+        for k in [
+            BigUint::zero(),
+            BigUint::one(),
+            BigUint::from(0xdeadbeef_u32),
+            &n - BigUint::one(),
+            n.clone(),
+        ] {
+            assert_eq!(constant_time_mul(&g, &k, 256), g.scalar_mul(k.clone()));
+        }
+    }
+}
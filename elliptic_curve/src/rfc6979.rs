@@ -0,0 +1,159 @@
+use crate::point::to_fixed_bytes;
+use digest::core_api::BlockSizeUser;
+use digest::Digest;
+use hmac::{Mac, SimpleHmac};
+use num_bigint::BigUint;
+use num_traits::Zero;
+use sha2::Sha256;
+use std::marker::PhantomData;
+
+/// Deterministic nonces of RFC 6979 with HMAC-DRBG over the hash D, SHA-256 by default
+///
+/// every call to next returns a candidate k in [1, q-1], the next candidate is only
+/// needed when k gives r = 0 or s = 0.
+/// https://www.rfc-editor.org/rfc/rfc6979.html#section-3.2
+pub struct Rfc6979<D = Sha256> {
+    q: BigUint,
+    k: Vec<u8>,
+    v: Vec<u8>,
+    started: bool,
+    _digest: PhantomData<D>,
+}
+
+impl Rfc6979 {
+    /// create the generator for the private key x, the order q and the message hash h1
+    ///
+    /// extra is the optional additional data k' of section 3.6, it is mixed in the seed
+    /// so signatures are randomized but still safe with a bad RNG, empty for plain RFC 6979
+    pub fn new(x: &BigUint, q: &BigUint, h1: &[u8], extra: &[u8]) -> Self {
+        Self::with_digest(x, q, h1, extra)
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Rfc6979<D> {
+    /// create the generator with HMAC over D, which should be the hash of the message
+    ///
+    /// x is reduced modulo q as int2octets only encodes integers below q
+    pub fn with_digest(x: &BigUint, q: &BigUint, h1: &[u8], extra: &[u8]) -> Self {
+        let rlen = q.bits().div_ceil(8) as usize;
+        let x = to_fixed_bytes(&(x % q), rlen);
+        let h = to_fixed_bytes(&(bits2int(h1, q.bits()) % q), rlen);
+        let hlen = <D as Digest>::output_size();
+        let mut drbg = Self {
+            q: q.clone(),
+            k: vec![0_u8; hlen],
+            v: vec![1_u8; hlen],
+            started: false,
+            _digest: PhantomData,
+        };
+        for i in [0_u8, 1] {
+            drbg.k = drbg.hmac(&[&drbg.v, &[i], &x, &h, extra]);
+            drbg.v = drbg.hmac(&[&drbg.v]);
+        }
+        drbg
+    }
+
+    fn hmac(&self, data: &[&[u8]]) -> Vec<u8> {
+        let mut mac = SimpleHmac::<D>::new_from_slice(&self.k).unwrap();
+        for d in data {
+            mac.update(d);
+        }
+        mac.finalize().into_bytes().to_vec()
+    }
+}
+
+impl<D: Digest + BlockSizeUser> Iterator for Rfc6979<D> {
+    type Item = BigUint;
+
+    fn next(&mut self) -> Option<BigUint> {
+        let qlen = self.q.bits();
+        loop {
+            if self.started {
+                self.k = self.hmac(&[&self.v, &[0]]);
+                self.v = self.hmac(&[&self.v]);
+            }
+            self.started = true;
+            let mut t = Vec::new();
+            while (t.len() as u64) * 8 < qlen {
+                self.v = self.hmac(&[&self.v]);
+                t.extend_from_slice(&self.v);
+            }
+            let k = bits2int(&t, qlen);
+            if !k.is_zero() && k < self.q {
+                return Some(k);
+            }
+        }
+    }
+}
+
+/// return the first nonce of RFC 6979 for the private key x, the order q and the hash h1
+pub fn generate_k(x: &BigUint, q: &BigUint, h1: &[u8], extra: &[u8]) -> BigUint {
+    Rfc6979::new(x, q, h1, extra).next().unwrap()
+}
+
+/// the leftmost qlen bits of b as an integer, section 2.3.2
+pub fn bits2int(b: &[u8], qlen: u64) -> BigUint {
+    let x = BigUint::from_bytes_be(b);
+    let blen = b.len() as u64 * 8;
+    if blen > qlen {
+        x >> (blen - qlen)
+    } else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curve::from_hex;
+    use sha2::{Digest, Sha256};
+
+    #[test]
+    fn test_generate_k_163_bits() {
+        // RFC 6979 appendix A.1, qlen = 163 < 256 so h1 is truncated
+        let q = from_hex("4000000000000000000020108a2e0cc0d99f8a5ef");
+        let x = from_hex("09a4d6792295a7f730fc3f2b49cbc0f62e862272f");
+        let h1 = Sha256::digest(b"sample");
+        assert_eq!(
+            generate_k(&x, &q, &h1, &[]),
+            from_hex("23af4074c90a02b3fe61d286d5c87f425e6bdd81b")
+        );
+    }
+
+    #[test]
+    fn test_generate_k_secp256k1() {
+        let q = from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let h1 = Sha256::digest(b"Satoshi Nakamoto");
+        assert_eq!(
+            generate_k(&BigUint::from(1_u32), &q, &h1, &[]),
+            from_hex("8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15")
+        );
+    }
+
+    #[test]
+    fn test_extra_entropy() {
+        let q = from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let x = BigUint::from(1_u32);
+        let h1 = Sha256::digest(b"sample");
+        let k = generate_k(&x, &q, &h1, &[]);
+        assert_ne!(generate_k(&x, &q, &h1, b"extra"), k);
+        assert_eq!(
+            generate_k(&x, &q, &h1, b"extra"),
+            generate_k(&x, &q, &h1, b"extra")
+        );
+        let mut nonces = Rfc6979::new(&x, &q, &h1, &[]);
+        assert_eq!(nonces.next(), Some(k.clone()));
+        assert_ne!(nonces.next(), Some(k));
+    }
+
+    #[test]
+    fn test_large_private_key() {
+        // x >= 2^(8 * rlen) used to underflow the padding of int2octets
+        let q = from_hex("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141");
+        let x = BigUint::from(1_u32);
+        let h1 = Sha256::digest(b"sample");
+        let k = generate_k(&x, &q, &h1, &[]);
+        assert_eq!(generate_k(&(&x + &q), &q, &h1, &[]), k);
+        assert_eq!(generate_k(&((&q << 64) + &x), &q, &h1, &[]), k);
+    }
+}
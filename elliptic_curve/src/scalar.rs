@@ -0,0 +1,192 @@
+use crate::curve::CurveParams;
+use crate::point::to_fixed_bytes;
+use finite_field::ff::FiniteField;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use std::marker::PhantomData;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Element of the scalar field Z_n of the curve C, n is the order of the generator
+///
+/// scalars of different curves have different types like points
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scalar<C: CurveParams> {
+    value: FF,
+    _curve: PhantomData<C>,
+}
+
+impl<C: CurveParams> Scalar<C> {
+    /// Create a new scalar, it is reduced modulo n
+    pub fn new(num: BigUint) -> Self {
+        Self::from_ff(FF::new(num, C::order()))
+    }
+
+    /// return the scalar 0
+    pub fn zero() -> Self {
+        Self::new(BigUint::zero())
+    }
+
+    /// return the scalar 1
+    pub fn one() -> Self {
+        Self::new(BigUint::one())
+    }
+
+    /// return a random non-zero scalar
+    pub fn random() -> Self {
+        let mut rng = rand::thread_rng();
+        Self::new(rng.gen_biguint_range(&BigUint::one(), &C::order()))
+    }
+
+    /// return the scalar as an integer in [0, n)
+    pub fn value(&self) -> &BigUint {
+        &self.value.num
+    }
+
+    /// return true if the scalar is 0
+    pub fn is_zero(&self) -> bool {
+        self.value.num.is_zero()
+    }
+
+    /// return the inverse modulo n, panic if the scalar is 0
+    pub fn inverse(&self) -> Self {
+        Self::from_ff(self.value.inverse())
+    }
+
+    /// return the scalar raised to the power of exp
+    pub fn pow(&self, exp: &BigUint) -> Self {
+        Self::new(self.value.num.modpow(exp, &self.value.prime))
+    }
+
+    /// big-endian bytes with the length of n
+    pub fn to_bytes(&self) -> Vec<u8> {
+        to_fixed_bytes(&self.value.num, C::order().bits().div_ceil(8) as usize)
+    }
+
+    fn from_ff(value: FF) -> Self {
+        Self {
+            value,
+            _curve: PhantomData,
+        }
+    }
+}
+
+impl<C: CurveParams> From<BigUint> for Scalar<C> {
+    fn from(num: BigUint) -> Self {
+        Self::new(num)
+    }
+}
+
+impl<C: CurveParams> From<u64> for Scalar<C> {
+    fn from(num: u64) -> Self {
+        Self::new(BigUint::from(num))
+    }
+}
+
+impl<C: CurveParams> Neg for Scalar<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_ff(self.value.to_zero() - &self.value)
+    }
+}
+
+impl<C: CurveParams> Add for Scalar<C> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl<C: CurveParams> Sub for Scalar<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self - &rhs
+    }
+}
+
+impl<C: CurveParams> Mul for Scalar<C> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl<C: CurveParams> Div for Scalar<C> {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self / &rhs
+    }
+}
+
+impl<C: CurveParams> Add<&Self> for Scalar<C> {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        Self::from_ff(self.value + &rhs.value)
+    }
+}
+
+impl<C: CurveParams> Sub<&Self> for Scalar<C> {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        Self::from_ff(self.value - &rhs.value)
+    }
+}
+
+impl<C: CurveParams> Mul<&Self> for Scalar<C> {
+    type Output = Self;
+
+    fn mul(self, rhs: &Self) -> Self::Output {
+        Self::from_ff(self.value * &rhs.value)
+    }
+}
+
+impl<C: CurveParams> Div<&Self> for Scalar<C> {
+    type Output = Self;
+
+    fn div(self, rhs: &Self) -> Self::Output {
+        Self::from_ff(self.value / &rhs.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::{Secp256k1, Secp256k1Params};
+
+    type S = Scalar<Secp256k1Params>;
+
+    #[test]
+    fn test_scalar_arithmetic() {
+        let n = Secp256k1Params::order();
+        let a = S::from(7_u64);
+        let b = S::new(&n + BigUint::from(3_u32));
+        assert_eq!(b.value(), &BigUint::from(3_u32));
+        assert_eq!(a.clone() + &b, S::from(10_u64));
+        assert_eq!(b.clone() - &a, S::new(&n - BigUint::from(4_u32)));
+        assert_eq!(a.clone() * &b, S::from(21_u64));
+        assert_eq!((a.clone() / &b) * &b, a);
+        assert_eq!(a.clone() * &a.inverse(), S::one());
+        assert_eq!(-a.clone() + &a, S::zero());
+        assert_eq!(a.pow(&(&n - BigUint::one())), S::one());
+        assert!(S::zero().is_zero());
+        assert_eq!(S::one().to_bytes().len(), 32);
+        assert!(!S::random().is_zero());
+    }
+
+    #[test]
+    fn test_scalar_point_mul() {
+        let g = Secp256k1::new().g().clone();
+        let (a, b) = (S::random(), S::random());
+        // (a + b)G = aG + bG and (ab)G = a(bG)
+        assert_eq!(&g * &(a.clone() + &b), &g * &a + &g * &b);
+        assert_eq!(&g * &(a.clone() * &b), &(&g * &b) * &a);
+        assert!((&g * &S::new(Secp256k1Params::order())).is_infinity());
+    }
+}
@@ -0,0 +1,338 @@
+use crate::curve::{Curve, CurveParams, CurvePoint};
+use crate::point::{to_fixed_bytes, ECCPoint, Point};
+use crate::secp256k1::{Secp256k1, Secp256k1Params};
+use crate::signature::{Signer, Verifier};
+use num_bigint::{BigUint, RandBigInt};
+use num_traits::{One, Zero};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+/// BIP-340 Schnorr signature R.x || s
+/// https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchnorrSignature {
+    r: BigUint,
+    s: BigUint,
+}
+
+impl SchnorrSignature {
+    /// decode the 64 bytes signature, return None if r >= p or s >= n
+    pub fn from_bytes(bytes: &[u8; 64]) -> Option<Self> {
+        let r = BigUint::from_bytes_be(&bytes[..32]);
+        let s = BigUint::from_bytes_be(&bytes[32..]);
+        if r >= Secp256k1Params::p() || s >= Secp256k1Params::order() {
+            return None;
+        }
+        Some(Self { r, s })
+    }
+
+    /// encode the signature as 64 bytes
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0_u8; 64];
+        bytes[..32].copy_from_slice(&to_fixed_bytes(&self.r, 32));
+        bytes[32..].copy_from_slice(&to_fixed_bytes(&self.s, 32));
+        bytes
+    }
+}
+
+/// BIP-340 private key
+///
+/// the secret is stored negated if d*G has an odd y so the public point has an even y,
+/// it is zeroized on drop
+pub struct SchnorrSigningKey {
+    secret: Vec<u8>,
+    verifying_key: SchnorrVerifyingKey,
+}
+
+impl SchnorrSigningKey {
+    /// create a signing key from the 32 bytes secret, return None if it is not in [1, n - 1]
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let d = BigUint::from_bytes_be(bytes);
+        if d.is_zero() || d >= Secp256k1Params::order() {
+            return None;
+        }
+        let point = Secp256k1::new().g().constant_time_mul(&d);
+        let (d, point) = if has_even_y(&point) {
+            (d, point)
+        } else {
+            (Secp256k1Params::order() - d, -point)
+        };
+        Some(Self {
+            secret: to_fixed_bytes(&d, 32),
+            verifying_key: SchnorrVerifyingKey { point },
+        })
+    }
+
+    /// create a random signing key
+    pub fn random<R: RngCore + CryptoRng>(rng: &mut R) -> Self {
+        let d = rng.gen_biguint_range(&BigUint::one(), &Secp256k1Params::order());
+        let bytes: [u8; 32] = to_fixed_bytes(&d, 32).try_into().unwrap();
+        Self::from_bytes(&bytes).unwrap()
+    }
+
+    /// return the x-only public key
+    pub fn verifying_key(&self) -> &SchnorrVerifyingKey {
+        &self.verifying_key
+    }
+
+    /// sign msg with the auxiliary random data aux_rand
+    ///
+    /// the nonce is the tagged hash of (d xor hash(aux_rand)) || P.x || msg and is negated
+    /// if R has an odd y. Return None if the nonce is zero as BIP-340 fails then, it only
+    /// happens with probability 2^-256
+    pub fn sign_with_aux(&self, msg: &[u8], aux_rand: &[u8; 32]) -> Option<SchnorrSignature> {
+        let e = Secp256k1::new();
+        let n = e.n();
+        let d = BigUint::from_bytes_be(&self.secret);
+        let t: Vec<u8> = self
+            .secret
+            .iter()
+            .zip(tagged_hash("BIP0340/aux", &[aux_rand]))
+            .map(|(a, b)| a ^ b)
+            .collect();
+        let p_bytes = self.verifying_key.to_bytes();
+        let k = BigUint::from_bytes_be(&tagged_hash("BIP0340/nonce", &[&t, &p_bytes, msg])) % n;
+        if k.is_zero() {
+            return None;
+        }
+        let big_r = e.g().constant_time_mul(&k);
+        let k = if has_even_y(&big_r) { k } else { n - k };
+        let r = big_r.x().num;
+        let challenge = challenge(&r, &p_bytes, msg);
+        let s = (k + challenge * d) % n;
+        Some(SchnorrSignature { r, s })
+    }
+}
+
+impl Drop for SchnorrSigningKey {
+    fn drop(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl Signer<SchnorrSignature> for SchnorrSigningKey {
+    /// sign msg with fresh auxiliary random data as BIP-340 recommends, new data is drawn
+    /// in the unlikely case of a zero nonce
+    fn sign(&self, msg: &[u8]) -> SchnorrSignature {
+        let mut rng = rand::thread_rng();
+        let mut aux_rand = [0_u8; 32];
+        loop {
+            rng.fill_bytes(&mut aux_rand);
+            if let Some(sig) = self.sign_with_aux(msg, &aux_rand) {
+                return sig;
+            }
+        }
+    }
+}
+
+/// BIP-340 x-only public key, the point with an even y
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchnorrVerifyingKey {
+    point: CurvePoint<Secp256k1Params>,
+}
+
+impl SchnorrVerifyingKey {
+    /// decode the 32 bytes x coordinate, return None if x >= p or it is not in the curve
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        let point = lift_x_even(&BigUint::from_bytes_be(bytes))?;
+        Some(Self { point })
+    }
+
+    /// encode the x coordinate as 32 bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        to_fixed_bytes(&self.point.x().num, 32).try_into().unwrap()
+    }
+
+    /// return the public point
+    pub fn as_point(&self) -> &CurvePoint<Secp256k1Params> {
+        &self.point
+    }
+}
+
+impl Verifier<SchnorrSignature> for SchnorrVerifyingKey {
+    /// check that R = s*G - e*P is not infinity, has an even y and R.x = r
+    fn verify(&self, msg: &[u8], signature: &SchnorrSignature) -> bool {
+        let e = Secp256k1::new();
+        let n = e.n();
+        let challenge = challenge(&signature.r, &self.to_bytes(), msg);
+        let big_r = e
+            .g()
+            .shamir_mul(&signature.s, &self.point, &((n - challenge) % n));
+        !big_r.is_infinity() && has_even_y(&big_r) && big_r.x().num == signature.r
+    }
+}
+
+/// Schnorr signature of a message to verify in a batch, with its public key
+#[derive(Debug, Clone)]
+pub struct SchnorrBatchItem<'a> {
+    pub key: &'a SchnorrVerifyingKey,
+    pub msg: &'a [u8],
+    pub sig: &'a SchnorrSignature,
+}
+
+/// verify many BIP-340 signatures at once
+///
+/// with a_1 = 1 and random 128 bits a_i, (sum a_i*s_i)*G = sum a_i*R_i + sum a_i*e_i*P_i
+/// is a single multi scalar multiplication of 2m + 1 points.
+///
+/// return Ok if all the signatures are valid, otherwise every item is verified alone and
+/// Err gives the indices of the invalid ones
+pub fn schnorr_verify_batch(items: &[SchnorrBatchItem]) -> Result<(), Vec<usize>> {
+    if batch_equation(items) {
+        return Ok(());
+    }
+    let invalid: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| !item.key.verify(item.msg, item.sig))
+        .map(|(i, _)| i)
+        .collect();
+    Err(invalid)
+}
+
+fn batch_equation(items: &[SchnorrBatchItem]) -> bool {
+    let e = Secp256k1::new();
+    let n = e.n();
+    let mut rng = rand::thread_rng();
+    let mut points = vec![e.g().clone()];
+    let mut scalars = vec![BigUint::zero()];
+    for (i, item) in items.iter().enumerate() {
+        let big_r = match lift_x_even(&item.sig.r) {
+            Some(big_r) => big_r,
+            None => return false,
+        };
+        let a = match i {
+            0 => BigUint::one(),
+            _ => rng.gen_biguint(128),
+        };
+        let challenge = challenge(&item.sig.r, &item.key.to_bytes(), item.msg);
+        scalars[0] = (&scalars[0] + &a * &item.sig.s) % n;
+        points.push(big_r);
+        scalars.push((n - &a) % n);
+        points.push(item.key.point.clone());
+        scalars.push((n - a * challenge % n) % n);
+    }
+    CurvePoint::msm(&points, &scalars).unwrap().is_infinity()
+}
+
+/// SHA-256(SHA-256(tag) || SHA-256(tag) || parts)
+pub fn tagged_hash(tag: &str, parts: &[&[u8]]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(tag_hash);
+    hasher.update(tag_hash);
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().into()
+}
+
+/// e = hash(R.x || P.x || msg) mod n
+fn challenge(r: &BigUint, p: &[u8; 32], msg: &[u8]) -> BigUint {
+    let r = to_fixed_bytes(r, 32);
+    BigUint::from_bytes_be(&tagged_hash("BIP0340/challenge", &[&r, p, msg]))
+        % Secp256k1Params::order()
+}
+
+/// the point with an even y and the given x, None if x >= p or x is not in the curve
+fn lift_x_even(x: &BigUint) -> Option<CurvePoint<Secp256k1Params>> {
+    if x >= &Secp256k1Params::p() {
+        return None;
+    }
+    let e = Curve::<Secp256k1Params>::new();
+    let point = ECCPoint::lift_x(&Secp256k1Params::field(x.clone()), false, &e.a, &e.b)?;
+    Some(CurvePoint::from_ecc(point))
+}
+
+fn has_even_y(p: &CurvePoint<Secp256k1Params>) -> bool {
+    !p.y().num.bit(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::hex;
+
+    /// https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+    #[test]
+    fn test_bip340_vectors() {
+        let csv = include_str!("../test_vectors/bip340.csv");
+        for line in csv.lines().skip(1) {
+            let fields: Vec<&str> = line.split(',').collect();
+            let [index, secret, public, aux, msg, sig, result, ..] = fields[..] else {
+                panic!("invalid line {line}");
+            };
+            let msg = hex(msg);
+            let sig: [u8; 64] = hex(sig).try_into().unwrap();
+            let public: [u8; 32] = hex(public).try_into().unwrap();
+            if !secret.is_empty() {
+                let secret: [u8; 32] = hex(secret).try_into().unwrap();
+                let aux: [u8; 32] = hex(aux).try_into().unwrap();
+                let signing_key = SchnorrSigningKey::from_bytes(&secret).unwrap();
+                assert_eq!(signing_key.verifying_key().to_bytes(), public, "{index}");
+                let signature = signing_key.sign_with_aux(&msg, &aux).unwrap();
+                assert_eq!(signature.to_bytes(), sig, "{index}");
+            }
+            let valid = match (
+                SchnorrVerifyingKey::from_bytes(&public),
+                SchnorrSignature::from_bytes(&sig),
+            ) {
+                (Some(key), Some(sig)) => key.verify(&msg, &sig),
+                _ => false,
+            };
+            assert_eq!(valid, result == "TRUE", "{index}");
+        }
+    }
+
+    #[test]
+    fn test_sign_verify() {
+        let mut rng = rand::thread_rng();
+        let signing_key = SchnorrSigningKey::random(&mut rng);
+        let sig = signing_key.sign(b"no.more.caffeine");
+        let verifying_key = signing_key.verifying_key();
+        assert!(verifying_key.verify(b"no.more.caffeine", &sig));
+        assert!(!verifying_key.verify(b"no.more.tea", &sig));
+        // aux_rand changes the signature
+        assert_ne!(sig, signing_key.sign(b"no.more.caffeine"));
+        let decoded = SchnorrSignature::from_bytes(&sig.to_bytes()).unwrap();
+        assert_eq!(decoded, sig);
+    }
+
+    #[test]
+    fn test_verify_batch() {
+        let mut rng = rand::thread_rng();
+        let keys: Vec<SchnorrSigningKey> = (0..4)
+            .map(|_| SchnorrSigningKey::random(&mut rng))
+            .collect();
+        let msgs: Vec<Vec<u8>> = (0..20_u32).map(|i| i.to_be_bytes().to_vec()).collect();
+        let mut sigs: Vec<SchnorrSignature> = msgs
+            .iter()
+            .enumerate()
+            .map(|(i, msg)| keys[i % 4].sign(msg))
+            .collect();
+        fn items<'a>(
+            keys: &'a [SchnorrSigningKey],
+            msgs: &'a [Vec<u8>],
+            sigs: &'a [SchnorrSignature],
+        ) -> Vec<SchnorrBatchItem<'a>> {
+            sigs.iter()
+                .enumerate()
+                .map(|(i, sig)| SchnorrBatchItem {
+                    key: keys[i % 4].verifying_key(),
+                    msg: &msgs[i],
+                    sig,
+                })
+                .collect()
+        }
+        assert_eq!(schnorr_verify_batch(&items(&keys, &msgs, &sigs)), Ok(()));
+        assert_eq!(schnorr_verify_batch(&[]), Ok(()));
+
+        sigs[2] = sigs[6].clone();
+        sigs[13].s = (&sigs[13].s + 1_u32) % Secp256k1Params::order();
+        assert_eq!(
+            schnorr_verify_batch(&items(&keys, &msgs, &sigs)),
+            Err(vec![2, 13])
+        );
+    }
+}
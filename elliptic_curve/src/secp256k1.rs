@@ -1,102 +1,80 @@
-use crate::point::ECCPoint as Point;
-use crate::point::Point as point;
-use crate::point::PointData;
-use finite_field::ff::FiniteField;
-use finite_field::helper::sqrt_root;
-use finite_field::prime_field::PrimeField as FF;
-use num_bigint::BigUint;
-use num_traits::{Num, Zero};
+use crate::curve::{from_hex, Curve, CurveParams};
+use crate::glv::Glv;
+use crate::hash_to_curve::HashToCurveParams;
+use num_bigint::BigInt;
 
-#[derive(Debug, Clone)]
-pub struct Secp256k1 {
-    pub a: FF,
-    pub b: FF,
-    pub p: BigUint,
-    pub g: Point,
-    pub n: BigUint,
-}
-
-impl Default for Secp256k1 {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-/// secp256k1 curve which is used in bitcoin
+/// Parameters of the secp256k1 curve which is used in bitcoin
 /// https://en.bitcoin.it/wiki/Secp256k1
-impl Secp256k1 {
-    /// Create a new secp256k1 curve
-    pub fn new() -> Self {
-        let p = BigUint::from_str_radix(
-            "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
-            16,
-        )
-        .unwrap();
-        let a = FF::new(BigUint::zero(), p.clone());
-        let b = FF::new(BigUint::from(7_u32), p.clone());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Secp256k1Params;
 
-        let x = BigUint::from_str_radix(
-            "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
-            16,
-        )
-        .unwrap();
-        let y = BigUint::from_str_radix(
-            "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
-            16,
-        )
-        .unwrap();
-        let x = FF::new(x, p.clone());
-        let y = FF::new(y, p.clone());
-        let g: PointData = (Some(x), Some(y), a.clone(), b.clone());
-        let g = Point::new(&g);
+impl CurveParams for Secp256k1Params {
+    const NAME: &'static str = "secp256k1";
+    const P: &'static str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+    const A: &'static str = "0";
+    const B: &'static str = "7";
+    const GX: &'static str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const GY: &'static str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+    const N: &'static str = "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+    const H: &'static str = "1";
 
-        let n = BigUint::from_str_radix(
-            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
-            16,
-        )
-        .unwrap();
-        Self { a, b, p, g, n }
-    }
-
-    /// Return the generator point
-    pub fn g(&self) -> &Point {
-        &self.g
-    }
-
-    /// return the n which is the order of the generator point
-    pub fn n(&self) -> &BigUint {
-        &self.n
+    /// beta and lambda are the cube roots of unity modulo p and n with lambda*(x, y) = (beta*x, y)
+    ///
+    /// the basis is the one of libsecp256k1
+    fn glv() -> Option<Glv> {
+        let a1 = BigInt::from(from_hex("3086d221a7d46bcde86c90e49284eb15"));
+        Some(Glv {
+            beta: from_hex("7ae96a2b657c07106e64479eac3434e99cf0497512f58995c1396c28719501ee"),
+            lambda: from_hex("5363ad4cc05c30e0a5261c028812645a122e22ea20816678df02967c1b23bd72"),
+            n: Self::order(),
+            a1: a1.clone(),
+            b1: -BigInt::from(from_hex("e4437ed6010e88286f547fa90abfe4c3")),
+            a2: BigInt::from(from_hex("114ca50f7a8e2f3f657c1108d9d44cfd8")),
+            b2: a1,
+        })
     }
+}
 
-    /// create a new point
-    pub fn point(&self, x: BigUint, y: BigUint) -> Point {
-        let p: PointData = (
-            Some(FF::new(x, self.p.clone())),
-            Some(FF::new(y, self.p.clone())),
-            self.a.clone(),
-            self.b.clone(),
-        );
-        Point::new(&p)
-    }
-
-    /// lift x to a point which mean return a point (x, y) such that y^2 = x^3 + ax + b mod p
-    pub fn lift_x(&self, x: &BigUint) -> Point {
-        let y = x.pow(3) + &self.a.num * x + &self.b.num;
-        let y = sqrt_root(y, self.p.clone());
-
-        let p: PointData = (
-            Some(FF::new(x.clone(), self.p.clone())),
-            Some(FF::new(y, self.p.clone())),
-            self.a.clone(),
-            self.b.clone(),
-        );
-        Point::new(&p)
-    }
+/// suite secp256k1_XMD:SHA-256_SSWU_RO_ of RFC 9380 section 8.7
+///
+/// a = 0 so the SWU map goes to an isogenous curve which is sent back with a 3-isogeny
+impl HashToCurveParams for Secp256k1Params {
+    const Z: &'static str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc24";
+    const SSWU_A: &'static str = "3f8731abdd661adca08a5558f0f5d272e953d363cb6f0e5d405447c01a444533";
+    const SSWU_B: &'static str = "6eb";
+    const ISO_X_NUM: &'static [&'static str] = &[
+        "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa8c7",
+        "07d3d4c80bc321d5b9f315cea7fd44c5d595d2fc0bf63b92dfff1044f17c6581",
+        "534c328d23f234e6e2a413deca25caece4506144037c40314ecbd0b53d9dd262",
+        "8e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38e38daaaaa88c",
+    ];
+    const ISO_X_DEN: &'static [&'static str] = &[
+        "d35771193d94918a9ca34ccbb7b640dd86cd409542f8487d9fe6b745781eb49b",
+        "edadc6f64383dc1df7c4b2d51b54225406d36b641f5e41bbc52a56612a8c6d14",
+        "1",
+    ];
+    const ISO_Y_NUM: &'static [&'static str] = &[
+        "4bda12f684bda12f684bda12f684bda12f684bda12f684bda12f684b8e38e23c",
+        "c75e0c32d5cb7c0fa9d0a54b12a0a6d5647ab046d686da6fdffc90fc201d71a3",
+        "29a6194691f91a73715209ef6512e576722830a201be2018a765e85a9ecee931",
+        "2f684bda12f684bda12f684bda12f684bda12f684bda12f684bda12f38e38d84",
+    ];
+    const ISO_Y_DEN: &'static [&'static str] = &[
+        "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffff93b",
+        "7a06534bb8bdb49fd5e9e6632722c2989467c1bfc8e8d978dfb425d2685c2573",
+        "6484aa716545ca2cf3a70c3fa8fe337e0a3d21162f0d6299a7bf8192bfd2a76f",
+        "1",
+    ];
 }
 
+/// secp256k1 curve which is used in bitcoin
+pub type Secp256k1 = Curve<Secp256k1Params>;
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use num_bigint::{BigUint, RandBigInt};
+    use num_traits::Num;
 
     #[test]
     pub fn test_secp256k1() {
@@ -127,6 +105,29 @@ mod tests {
             16,
         )
         .unwrap();
-        assert_eq!(secp256k1.lift_x(x), secp256k1.g);
+        assert_eq!(secp256k1.lift_x(x, false), Some(secp256k1.g.clone()));
+        assert_eq!(
+            secp256k1.lift_x(x, true),
+            Some(secp256k1.g.scalar_mul(secp256k1.n() - BigUint::from(1_u32)))
+        );
+    }
+
+    #[test]
+    pub fn test_glv_scalar_mul() {
+        // the typed point uses GLV, the untyped point the generic w-NAF
+        let mut rng = rand::thread_rng();
+        let secp256k1 = Secp256k1::new();
+        let g = secp256k1.g();
+        let q = g.scalar_mul(rng.gen_biguint(256));
+        for _ in 0..10 {
+            let (k1, k2) = (rng.gen_biguint(256), rng.gen_biguint(256));
+            let expected = g.as_ecc().scalar_mul(k1.clone());
+            assert_eq!((g * &k1).as_ecc(), &expected);
+            assert_eq!(g.constant_time_mul(&k1).as_ecc(), &expected);
+            assert_eq!(
+                g.shamir_mul(&k1, &q, &k2).as_ecc(),
+                &(expected + q.as_ecc().scalar_mul(k2))
+            );
+        }
     }
 }
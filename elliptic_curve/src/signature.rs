@@ -0,0 +1,11 @@
+/// Sign a message, implemented by the private keys of the signature schemes
+pub trait Signer<S> {
+    /// sign the message msg
+    fn sign(&self, msg: &[u8]) -> S;
+}
+
+/// Verify a signature, implemented by the public keys of the signature schemes
+pub trait Verifier<S> {
+    /// return true if signature is a valid signature of msg
+    fn verify(&self, msg: &[u8], signature: &S) -> bool;
+}
@@ -0,0 +1,66 @@
+use crate::curve::{from_hex, Curve, CurveParams};
+use crate::point::{ECCPoint, Point, PointData};
+use finite_field::ff::FiniteField;
+use finite_field::prime_field::PrimeField as FF;
+use num_bigint::BigUint;
+
+/// y^2 = x^3 + 2x + 3 over F_97 which has 100 points, G = (0, 10) has order 50
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Toy;
+
+impl CurveParams for Toy {
+    const NAME: &'static str = "toy";
+    const P: &'static str = "61";
+    const A: &'static str = "2";
+    const B: &'static str = "3";
+    const GX: &'static str = "0";
+    const GY: &'static str = "a";
+    const N: &'static str = "32";
+    const H: &'static str = "2";
+}
+
+/// the point (3, 6) of y^2 = x^3 + 2x + 3 over F_97
+pub(crate) fn small_curve_point() -> ECCPoint {
+    let p = BigUint::from(97_u32);
+    let point: PointData = (
+        Some(FF::new(BigUint::from(3_u32), p.clone())),
+        Some(FF::new(BigUint::from(6_u32), p.clone())),
+        FF::new(BigUint::from(2_u32), p.clone()),
+        FF::new(BigUint::from(3_u32), p),
+    );
+    ECCPoint::new(&point)
+}
+
+/// decode a hex string of test vectors
+pub(crate) fn hex(s: &str) -> Vec<u8> {
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// decode a hex string of test vectors of a fixed length, panic if it is another length
+pub(crate) fn hex_array<const N: usize>(s: &str) -> [u8; N] {
+    hex(s).try_into().expect("wrong length of the hex string")
+}
+
+/// check a NIST prime curve: a = -3, n*G is infinity, the group law and the (k, x, y)
+/// vectors of k*G
+pub(crate) fn check_nist_curve<C: CurveParams>(vectors: &[(u64, &str, &str)]) {
+    let curve = Curve::<C>::new();
+    assert_eq!(
+        curve.a.clone() + &C::field(BigUint::from(3_u32)),
+        curve.a.to_zero()
+    );
+    assert!(curve.g().scalar_mul(curve.n().clone()).is_infinity());
+    assert_eq!(
+        curve.g().scalar_mul(BigUint::from(100_u32)) + curve.g(),
+        curve.g().scalar_mul(BigUint::from(101_u32))
+    );
+    for (k, x, y) in vectors {
+        assert_eq!(
+            curve.g().scalar_mul(BigUint::from(*k)),
+            curve.point(from_hex(x), from_hex(y))
+        );
+    }
+}
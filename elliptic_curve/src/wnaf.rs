@@ -0,0 +1,284 @@
+use crate::jacobian::JacobianPoint;
+use crate::point::ECCPoint;
+use num_bigint::BigUint;
+use num_traits::{ToPrimitive, Zero};
+
+/// default window width of the w-NAF scalar multiplication
+pub const WNAF_WINDOW: usize = 4;
+
+/// width-w non-adjacent form of k, least significant digit first
+///
+/// every non-zero digit is odd with |d| < 2^(w-1) and is followed by at least w-1 zeros
+pub fn wnaf(k: &BigUint, w: usize) -> Vec<i8> {
+    if !(2..=8).contains(&w) {
+        panic!("window width must be between 2 and 8");
+    }
+    let modulus = 1_i32 << w;
+    let mut k = k.clone();
+    let mut digits = vec![];
+    while !k.is_zero() {
+        let mut d = 0_i32;
+        if k.bit(0) {
+            d = (&k % BigUint::from(modulus as u32)).to_i32().unwrap();
+            if d >= modulus / 2 {
+                d -= modulus;
+            }
+            if d > 0 {
+                k -= BigUint::from(d as u32);
+            } else {
+                k += BigUint::from((-d) as u32);
+            }
+        }
+        digits.push(d as i8);
+        k >>= 1;
+    }
+    digits
+}
+
+/// return [P, 3P, 5P, ..., (2^(w-1) - 1)P] in affine coordinates
+fn odd_multiples(p: &ECCPoint, w: usize) -> Vec<ECCPoint> {
+    let jp = JacobianPoint::from_affine(p);
+    let p2 = jp.double();
+    let mut multiples = vec![jp];
+    for i in 1..1 << (w - 2) {
+        let next = multiples[i - 1].clone() + &p2;
+        multiples.push(next);
+    }
+    JacobianPoint::batch_normalize(&multiples)
+}
+
+/// add d*P to r where table holds the odd multiples of P
+fn add_digit(r: JacobianPoint, d: i8, table: &[ECCPoint]) -> JacobianPoint {
+    match d {
+        0 => r,
+        d if d > 0 => r.add_mixed(&table[(d as usize - 1) / 2]),
+        d => r.add_mixed(&-&table[((-d) as usize - 1) / 2]),
+    }
+}
+
+/// k*P with the w-NAF method
+///
+/// precompute the odd multiples of P, then only one addition every w+1 bits on average
+pub fn wnaf_mul(p: &ECCPoint, k: &BigUint, w: usize) -> ECCPoint {
+    let digits = wnaf(k, w);
+    let table = odd_multiples(p, w);
+    let mut r = JacobianPoint::new_infinity_point(p.a.clone(), p.b.clone());
+    for &d in digits.iter().rev() {
+        r = add_digit(r.double(), d, &table);
+    }
+    r.to_affine()
+}
+
+/// k1*P + k2*Q with Shamir's trick
+///
+/// the w-NAF of both scalars are interleaved so the doublings are shared
+pub fn shamir_mul(p: &ECCPoint, k1: &BigUint, q: &ECCPoint, k2: &BigUint) -> ECCPoint {
+    straus_mul(&[p.clone(), q.clone()], &[k1.clone(), k2.clone()]).expect("not in the same curve")
+}
+
+/// sum k_i*P_i with Straus' method, this is Shamir's trick for any number of points
+///
+/// every point gets its own table of odd multiples, the doublings are shared by all of them.
+/// Return None if the input is not valid, see msm
+pub fn straus_mul(points: &[ECCPoint], scalars: &[BigUint]) -> Option<ECCPoint> {
+    if !is_valid_msm_input(points, scalars) {
+        return None;
+    }
+    let digits: Vec<Vec<i8>> = scalars.iter().map(|k| wnaf(k, WNAF_WINDOW)).collect();
+    let tables: Vec<Vec<ECCPoint>> = points
+        .iter()
+        .map(|p| odd_multiples(p, WNAF_WINDOW))
+        .collect();
+    let len = digits.iter().map(|d| d.len()).max().unwrap_or(0);
+    let mut r = JacobianPoint::new_infinity_point(points[0].a.clone(), points[0].b.clone());
+    for i in (0..len).rev() {
+        r = r.double();
+        for (d, table) in digits.iter().zip(&tables) {
+            r = add_digit(r, d.get(i).copied().unwrap_or(0), table);
+        }
+    }
+    Some(r.to_affine())
+}
+
+/// return true if there are as many points as scalars, at least one, all in the same curve
+pub(crate) fn is_valid_msm_input(points: &[ECCPoint], scalars: &[BigUint]) -> bool {
+    points.len() == scalars.len()
+        && !points.is_empty()
+        && points
+            .iter()
+            .all(|p| p.a == points[0].a && p.b == points[0].b)
+}
+
+/// return the w bits of k starting at the bit start
+pub(crate) fn window_digit(k: &BigUint, start: usize, w: usize) -> usize {
+    let mut d = 0;
+    for j in (0..w).rev() {
+        d = (d << 1) | k.bit((start + j) as u64) as usize;
+    }
+    d
+}
+
+/// Precomputed table for a fixed base point B
+///
+/// the scalar is split in windows of w bits, the row i holds j*2^(wi)*B for 1 <= j < 2^w,
+/// so k*B costs one addition per window and no doubling
+#[derive(Debug, Clone)]
+pub struct FixedBaseTable {
+    base: ECCPoint,
+    w: usize,
+    rows: Vec<Vec<ECCPoint>>,
+}
+
+impl FixedBaseTable {
+    /// build the table for scalars up to bits bits
+    pub fn new(base: &ECCPoint, bits: u64, w: usize) -> Self {
+        if !(1..=8).contains(&w) {
+            panic!("window width must be between 1 and 8");
+        }
+        let windows = (bits as usize).div_ceil(w);
+        let mut b = JacobianPoint::from_affine(base);
+        let mut points = Vec::with_capacity(windows << w);
+        for _ in 0..windows {
+            let mut acc = b.clone();
+            for _ in 1..1 << w {
+                points.push(acc.clone());
+                acc = acc + &b;
+            }
+            // acc = 2^w * b
+            b = acc;
+        }
+        let rows = JacobianPoint::batch_normalize(&points)
+            .chunks((1 << w) - 1)
+            .map(|row| row.to_vec())
+            .collect();
+        Self {
+            base: base.clone(),
+            w,
+            rows,
+        }
+    }
+
+    /// return the base point
+    pub fn base(&self) -> &ECCPoint {
+        &self.base
+    }
+
+    /// k*B, fall back to w-NAF when k is larger than the table
+    pub fn mul(&self, k: &BigUint) -> ECCPoint {
+        if k.bits() as usize > self.rows.len() * self.w {
+            return wnaf_mul(&self.base, k, WNAF_WINDOW);
+        }
+        let mut r = JacobianPoint::new_infinity_point(self.base.a.clone(), self.base.b.clone());
+        for (i, row) in self.rows.iter().enumerate() {
+            let d = window_digit(k, i * self.w, self.w);
+            if d != 0 {
+                r = r.add_mixed(&row[d - 1]);
+            }
+        }
+        r.to_affine()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secp256k1::Secp256k1;
+    use num_bigint::{BigInt, RandBigInt};
+
+    /// affine double-and-add as the reference
+    fn naive_mul(p: &ECCPoint, k: &BigUint) -> ECCPoint {
+        let mut r = ECCPoint::new_infinity_point(p.a.clone(), p.b.clone());
+        for i in (0..k.bits()).rev() {
+            r = r.double();
+            if k.bit(i) {
+                r += p;
+            }
+        }
+        r
+    }
+
+    #[test]
+    fn test_wnaf() {
+        let mut rng = rand::thread_rng();
+        for w in 2..=8 {
+            let k = rng.gen_biguint(256);
+            let digits = wnaf(&k, w);
+            let mut value = BigInt::zero();
+            for (i, &d) in digits.iter().enumerate() {
+                value += BigInt::from(d) << i;
+                if d != 0 {
+                    assert_eq!(d % 2, 1 - 2 * (d < 0) as i8);
+                    assert!((d.unsigned_abs() as u32) < 1 << (w - 1));
+                    // at most one non-zero digit in any w consecutive digits
+                    let next = &digits[i + 1..(i + w).min(digits.len())];
+                    assert!(next.iter().all(|&d| d == 0));
+                }
+            }
+            assert_eq!(value, BigInt::from(k));
+        }
+        assert_eq!(wnaf(&BigUint::from(7_u32), 2), vec![-1, 0, 0, 1]);
+        assert!(wnaf(&BigUint::zero(), 4).is_empty());
+    }
+
+    #[test]
+    fn test_wnaf_mul() {
+        let mut rng = rand::thread_rng();
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let k = rng.gen_biguint(256);
+        let expected = naive_mul(&g, &k);
+        for w in [2, 4, 6] {
+            assert_eq!(wnaf_mul(&g, &k, w), expected);
+        }
+        assert!(wnaf_mul(&g, &BigUint::zero(), 4).is_infinity());
+        assert_eq!(wnaf_mul(&g, &BigUint::from(1_u32), 4), g);
+    }
+
+    #[test]
+    fn test_shamir_mul() {
+        let mut rng = rand::thread_rng();
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let q = g.scalar_mul(rng.gen_biguint(256));
+        let (k1, k2) = (rng.gen_biguint(256), rng.gen_biguint(128));
+        let expected = naive_mul(&g, &k1) + naive_mul(&q, &k2);
+        assert_eq!(shamir_mul(&g, &k1, &q, &k2), expected);
+        assert_eq!(shamir_mul(&g, &k1, &g, &k1), naive_mul(&g, &(&k1 << 1)));
+        assert!(shamir_mul(&g, &k1, &-&g, &k1).is_infinity());
+    }
+
+    #[test]
+    fn test_straus_mul() {
+        let mut rng = rand::thread_rng();
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let points: Vec<ECCPoint> = (0..4).map(|_| g.scalar_mul(rng.gen_biguint(256))).collect();
+        let scalars: Vec<BigUint> = (0..4).map(|_| rng.gen_biguint(256)).collect();
+        let infinity = ECCPoint::new_infinity_point(g.a.clone(), g.b.clone());
+        let expected = points
+            .iter()
+            .zip(&scalars)
+            .fold(infinity, |acc, (p, k)| acc + &naive_mul(p, k));
+        assert_eq!(straus_mul(&points, &scalars), Some(expected));
+    }
+
+    #[test]
+    fn test_straus_mul_invalid() {
+        let g = Secp256k1::new().g().as_ecc().clone();
+        assert_eq!(straus_mul(std::slice::from_ref(&g), &[]), None);
+        assert_eq!(straus_mul(&[], &[]), None);
+        let other = crate::test_utils::small_curve_point();
+        let k = BigUint::from(1_u32);
+        assert_eq!(straus_mul(&[g, other], &[k.clone(), k]), None);
+    }
+
+    #[test]
+    fn test_fixed_base_table() {
+        let mut rng = rand::thread_rng();
+        let g = Secp256k1::new().g().as_ecc().clone();
+        let table = FixedBaseTable::new(&g, 256, 4);
+        let k = rng.gen_biguint(256);
+        assert_eq!(table.mul(&k), naive_mul(&g, &k));
+        assert!(table.mul(&BigUint::zero()).is_infinity());
+        // larger than the table
+        let k = rng.gen_biguint(300);
+        assert_eq!(table.mul(&k), naive_mul(&g, &k));
+    }
+}
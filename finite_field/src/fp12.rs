@@ -0,0 +1,265 @@
+use crate::fp2::Fp2;
+use crate::fp6::Fp6;
+use num_bigint::BigUint;
+use num_traits::One;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Quadratic extension Fp12 = Fp6[w] / (w^2 - v), so w^6 = xi
+///
+/// the element c0 + c1*w, this is the target group of the BN and BLS12 pairings
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fp12 {
+    pub c0: Fp6,
+    pub c1: Fp6,
+}
+
+impl Fp12 {
+    /// Create a new element c0 + c1*w
+    pub fn new(c0: Fp6, c1: Fp6) -> Self {
+        if c0.xi != c1.xi {
+            panic!("Cannot create a number from different fields");
+        }
+        Self { c0, c1 }
+    }
+
+    /// Return the element 0 in Fp12
+    pub fn zero(xi: Fp2) -> Self {
+        Self {
+            c0: Fp6::zero(xi.clone()),
+            c1: Fp6::zero(xi),
+        }
+    }
+
+    /// Return the element 1 in Fp12
+    pub fn one(xi: Fp2) -> Self {
+        Self {
+            c0: Fp6::one(xi.clone()),
+            c1: Fp6::zero(xi),
+        }
+    }
+
+    /// Return the number with value 1 in the field
+    pub fn to_one(&self) -> Self {
+        Self::one(self.c0.xi.clone())
+    }
+
+    /// return true if the number is 1
+    pub fn is_one(&self) -> bool {
+        *self == self.to_one()
+    }
+
+    /// Return c0 - c1*w which is x^(p^6)
+    pub fn conjugate(&self) -> Self {
+        Self {
+            c0: self.c0.clone(),
+            c1: -self.c1.clone(),
+        }
+    }
+
+    /// Return x^2
+    pub fn square(&self) -> Self {
+        self.clone() * self
+    }
+
+    /// Return the inverse of the number
+    ///
+    /// (c0 + c1*w)^-1 = (c0 - c1*w) / (c0^2 - v*c1^2)
+    pub fn inverse(&self) -> Self {
+        let t = (self.c0.square() - &self.c1.square().mul_by_v()).inverse();
+        Self {
+            c0: self.c0.clone() * &t,
+            c1: -(self.c1.clone() * &t),
+        }
+    }
+
+    /// Return the number raised to the power of exp
+    pub fn pow(&self, exp: &BigUint) -> Self {
+        let mut result = self.to_one();
+        for i in (0..exp.bits()).rev() {
+            result = result.square();
+            if exp.bit(i) {
+                result = result * self;
+            }
+        }
+        result
+    }
+
+    /// Return x^(p^power)
+    ///
+    /// write x = sum a_i*w^i with a_i in Fp2, then x^p = sum conj(a_i)*gamma^i*w^i
+    /// with gamma = xi^((p-1)/6), this needs p = 1 mod 6
+    pub fn frobenius_map(&self, power: usize) -> Self {
+        let xi = &self.c0.xi;
+        let gamma = xi.pow(&((xi.prime() - BigUint::one()) / BigUint::from(6_u32)));
+        let mut gammas = vec![xi.to_one()];
+        for i in 1..6 {
+            gammas.push(gammas[i - 1].clone() * &gamma);
+        }
+
+        let mut coeffs = [
+            self.c0.c0.clone(),
+            self.c1.c0.clone(),
+            self.c0.c1.clone(),
+            self.c1.c1.clone(),
+            self.c0.c2.clone(),
+            self.c1.c2.clone(),
+        ];
+        for _ in 0..power {
+            for (coeff, gamma) in coeffs.iter_mut().zip(&gammas) {
+                *coeff = coeff.conjugate() * gamma;
+            }
+        }
+        let [a0, b0, a1, b1, a2, b2] = coeffs;
+        Self {
+            c0: Fp6::new(a0, a1, a2, xi.clone()),
+            c1: Fp6::new(b0, b1, b2, xi.clone()),
+        }
+    }
+}
+
+impl Neg for Fp12 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            c0: -self.c0,
+            c1: -self.c1,
+        }
+    }
+}
+
+impl Add for Fp12 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl Sub for Fp12 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self - &rhs
+    }
+}
+
+impl Mul for Fp12 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Div for Fp12 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self / &rhs
+    }
+}
+
+impl Add<&Self> for Fp12 {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        Self {
+            c0: self.c0 + &rhs.c0,
+            c1: self.c1 + &rhs.c1,
+        }
+    }
+}
+
+impl Sub<&Self> for Fp12 {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        Self {
+            c0: self.c0 - &rhs.c0,
+            c1: self.c1 - &rhs.c1,
+        }
+    }
+}
+
+impl Mul<&Self> for Fp12 {
+    type Output = Self;
+
+    /// karatsuba: (a0 + a1*w)(b0 + b1*w) = a0*b0 + v*a1*b1 + ((a0 + a1)(b0 + b1) - a0*b0 - a1*b1)*w
+    fn mul(self, rhs: &Self) -> Self::Output {
+        let t0 = self.c0.clone() * &rhs.c0;
+        let t1 = self.c1.clone() * &rhs.c1;
+        let c1 = (self.c0 + &self.c1) * &(rhs.c0.clone() + &rhs.c1) - &t0 - &t1;
+        Self {
+            c0: t0 + &t1.mul_by_v(),
+            c1,
+        }
+    }
+}
+
+impl Div<&Self> for Fp12 {
+    type Output = Self;
+
+    fn div(self, rhs: &Self) -> Self::Output {
+        self.mul(&rhs.inverse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff::FiniteField;
+    use crate::prime_field::PrimeField;
+
+    fn fp2(c0: u32, c1: u32) -> Fp2 {
+        let prime = BigUint::from(103_u32);
+        Fp2::new(
+            PrimeField::new(BigUint::from(c0), prime.clone()),
+            PrimeField::new(BigUint::from(c1), prime),
+        )
+    }
+
+    fn fp12(seed: u32) -> Fp12 {
+        // 2 + u is neither a square nor a cube in F_103^2
+        let xi = fp2(2, 1);
+        let fp6 = |s: u32| {
+            Fp6::new(
+                fp2(s, s + 1),
+                fp2(s + 2, s + 3),
+                fp2(s + 4, s + 5),
+                xi.clone(),
+            )
+        };
+        Fp12::new(fp6(seed), fp6(seed + 6))
+    }
+
+    #[test]
+    fn test_fp12_arithmetic() {
+        let a = fp12(3);
+        let b = fp12(20);
+        assert_eq!(a.clone() * &b, b.clone() * &a);
+        assert_eq!(a.square(), a.clone() * &a);
+        assert_eq!(a.clone() * &a.inverse(), a.to_one());
+        assert_eq!((a.clone() / &b) * &b, a);
+        // the multiplicative group has order 103^12 - 1
+        assert!(a
+            .pow(&(BigUint::from(103_u32).pow(12) - BigUint::one()))
+            .is_one());
+
+        // w^2 = v
+        let xi = a.c0.xi.clone();
+        let w = Fp12::new(Fp6::zero(xi.clone()), Fp6::one(xi.clone()));
+        let v = Fp6::new(xi.to_zero(), xi.to_one(), xi.to_zero(), xi.clone());
+        assert_eq!(w.square(), Fp12::new(v, Fp6::zero(xi)));
+    }
+
+    #[test]
+    fn test_fp12_frobenius() {
+        let a = fp12(5);
+        let p = BigUint::from(103_u32);
+        assert_eq!(a.frobenius_map(1), a.pow(&p));
+        assert_eq!(a.frobenius_map(2), a.pow(&(&p * &p)));
+        assert_eq!(a.frobenius_map(6), a.conjugate());
+        assert_eq!(a.frobenius_map(12), a);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::ff::FiniteField;
+use crate::prime_field::PrimeField;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Quadratic extension Fp2 = Fp[u] / (u^2 + 1)
+///
+/// the element c0 + c1*u, -1 must be a non-residue so p = 3 mod 4 (example: BN254, BLS12-381)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fp2 {
+    pub c0: PrimeField,
+    pub c1: PrimeField,
+}
+
+impl Fp2 {
+    /// Create a new element c0 + c1*u
+    pub fn new(c0: PrimeField, c1: PrimeField) -> Self {
+        if c0.prime != c1.prime {
+            panic!("Cannot create a number from different fields");
+        }
+        Self { c0, c1 }
+    }
+
+    /// Embed an element of the base field
+    pub fn from_base(c0: PrimeField) -> Self {
+        let c1 = c0.to_zero();
+        Self { c0, c1 }
+    }
+
+    /// Return the element 0 in Fp2
+    pub fn zero(prime: BigUint) -> Self {
+        Self::from_base(PrimeField::zero(prime))
+    }
+
+    /// Return the element 1 in Fp2
+    pub fn one(prime: BigUint) -> Self {
+        Self::from_base(PrimeField::new(BigUint::one(), prime))
+    }
+
+    /// Return the prime of the base field
+    pub fn prime(&self) -> &BigUint {
+        &self.c0.prime
+    }
+
+    /// Return the number with value 0 in the field
+    pub fn to_zero(&self) -> Self {
+        Self::zero(self.prime().clone())
+    }
+
+    /// Return the number with value 1 in the field
+    pub fn to_one(&self) -> Self {
+        Self::one(self.prime().clone())
+    }
+
+    /// return true if the number is 0
+    pub fn is_zero(&self) -> bool {
+        self.c0.num.is_zero() && self.c1.num.is_zero()
+    }
+
+    /// Return c0 - c1*u which is also the frobenius map x^p
+    pub fn conjugate(&self) -> Self {
+        Self {
+            c0: self.c0.clone(),
+            c1: self.c1.to_zero() - &self.c1,
+        }
+    }
+
+    /// Multiply by an element of the base field
+    pub fn scale(&self, k: &PrimeField) -> Self {
+        Self {
+            c0: self.c0.clone() * k,
+            c1: self.c1.clone() * k,
+        }
+    }
+
+    /// Return x^2
+    ///
+    /// (c0 + c1*u)^2 = (c0 + c1)(c0 - c1) + 2*c0*c1*u
+    pub fn square(&self) -> Self {
+        let c0c1 = self.c0.clone() * &self.c1;
+        Self {
+            c0: (self.c0.clone() + &self.c1) * &(self.c0.clone() - &self.c1),
+            c1: c0c1.clone() + &c0c1,
+        }
+    }
+
+    /// Return the inverse of the number
+    ///
+    /// (c0 + c1*u)^-1 = (c0 - c1*u) / (c0^2 + c1^2)
+    pub fn inverse(&self) -> Self {
+        if self.is_zero() {
+            panic!("0 is not invertible");
+        }
+        let norm = self.c0.pow(2) + &self.c1.pow(2);
+        self.conjugate().scale(&norm.inverse())
+    }
+
+    /// Return the number raised to the power of exp
+    pub fn pow(&self, exp: &BigUint) -> Self {
+        let mut result = self.to_one();
+        for i in (0..exp.bits()).rev() {
+            result = result.square();
+            if exp.bit(i) {
+                result = result * self;
+            }
+        }
+        result
+    }
+}
+
+impl Neg for Fp2 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.to_zero() - &self
+    }
+}
+
+impl Add for Fp2 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl Sub for Fp2 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self - &rhs
+    }
+}
+
+impl Mul for Fp2 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Div for Fp2 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self / &rhs
+    }
+}
+
+impl Add<&Self> for Fp2 {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        Self {
+            c0: self.c0 + &rhs.c0,
+            c1: self.c1 + &rhs.c1,
+        }
+    }
+}
+
+impl Sub<&Self> for Fp2 {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        Self {
+            c0: self.c0 - &rhs.c0,
+            c1: self.c1 - &rhs.c1,
+        }
+    }
+}
+
+impl Mul<&Self> for Fp2 {
+    type Output = Self;
+
+    /// (a0 + a1*u)(b0 + b1*u) = a0*b0 - a1*b1 + (a0*b1 + a1*b0)*u
+    fn mul(self, rhs: &Self) -> Self::Output {
+        let a0b0 = self.c0.clone() * &rhs.c0;
+        let a1b1 = self.c1.clone() * &rhs.c1;
+        let c1 = (self.c0 + &self.c1) * &(rhs.c0.clone() + &rhs.c1) - &a0b0 - &a1b1;
+        Self {
+            c0: a0b0 - &a1b1,
+            c1,
+        }
+    }
+}
+
+impl Div<&Self> for Fp2 {
+    type Output = Self;
+
+    fn div(self, rhs: &Self) -> Self::Output {
+        self.mul(&rhs.inverse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn fp2(c0: u32, c1: u32) -> Fp2 {
+        let prime = BigUint::from(103_u32);
+        Fp2::new(
+            PrimeField::new(BigUint::from(c0), prime.clone()),
+            PrimeField::new(BigUint::from(c1), prime),
+        )
+    }
+
+    #[test]
+    fn test_fp2_arithmetic() {
+        // over F_103: (3 + 5u)(7 + 2u) = 21 - 10 + (6 + 35)u
+        let a = fp2(3, 5);
+        let b = fp2(7, 2);
+        assert_eq!(a.clone() * &b, fp2(11, 41));
+        assert_eq!(a.clone() + &b, fp2(10, 7));
+        assert_eq!(a.clone() - &b, fp2(99, 3));
+        assert_eq!(a.square(), a.clone() * &a);
+        assert_eq!(fp2(0, 1).square(), fp2(102, 0));
+        assert_eq!(a.clone() * &a.inverse(), a.to_one());
+        assert_eq!((a.clone() / &b) * &b, a);
+        assert_eq!(-a.clone() + &a, a.to_zero());
+    }
+
+    #[test]
+    fn test_fp2_frobenius() {
+        let p = BigUint::from_str(
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+        )
+        .unwrap();
+        let a = Fp2::new(
+            PrimeField::new(BigUint::from(123456789_u32), p.clone()),
+            PrimeField::new(BigUint::from(987654321_u32), p.clone()),
+        );
+        assert_eq!(a.pow(&p), a.conjugate());
+        // the multiplicative group has order p^2 - 1
+        assert_eq!(a.pow(&(&p * &p - BigUint::one())), a.to_one());
+    }
+}
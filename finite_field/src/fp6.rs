@@ -0,0 +1,253 @@
+use crate::fp2::Fp2;
+use num_bigint::BigUint;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+/// Cubic extension Fp6 = Fp2[v] / (v^3 - xi)
+///
+/// the element c0 + c1*v + c2*v^2, xi must be neither a square nor a cube in Fp2
+/// (example: 9 + u for BN254, 1 + u for BLS12-381)
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fp6 {
+    pub c0: Fp2,
+    pub c1: Fp2,
+    pub c2: Fp2,
+    pub xi: Fp2,
+}
+
+impl Fp6 {
+    /// Create a new element c0 + c1*v + c2*v^2
+    pub fn new(c0: Fp2, c1: Fp2, c2: Fp2, xi: Fp2) -> Self {
+        Self { c0, c1, c2, xi }
+    }
+
+    /// Embed an element of Fp2
+    pub fn from_fp2(c0: Fp2, xi: Fp2) -> Self {
+        let zero = c0.to_zero();
+        Self {
+            c0,
+            c1: zero.clone(),
+            c2: zero,
+            xi,
+        }
+    }
+
+    /// Return the element 0 in Fp6
+    pub fn zero(xi: Fp2) -> Self {
+        Self::from_fp2(xi.to_zero(), xi)
+    }
+
+    /// Return the element 1 in Fp6
+    pub fn one(xi: Fp2) -> Self {
+        Self::from_fp2(xi.to_one(), xi)
+    }
+
+    /// Return the number with value 0 in the field
+    pub fn to_zero(&self) -> Self {
+        Self::zero(self.xi.clone())
+    }
+
+    /// Return the number with value 1 in the field
+    pub fn to_one(&self) -> Self {
+        Self::one(self.xi.clone())
+    }
+
+    /// return true if the number is 0
+    pub fn is_zero(&self) -> bool {
+        self.c0.is_zero() && self.c1.is_zero() && self.c2.is_zero()
+    }
+
+    /// Multiply by v, (c0 + c1*v + c2*v^2)*v = xi*c2 + c0*v + c1*v^2
+    pub fn mul_by_v(&self) -> Self {
+        Self {
+            c0: self.c2.clone() * &self.xi,
+            c1: self.c0.clone(),
+            c2: self.c1.clone(),
+            xi: self.xi.clone(),
+        }
+    }
+
+    /// Multiply by an element of Fp2
+    pub fn scale(&self, k: &Fp2) -> Self {
+        Self {
+            c0: self.c0.clone() * k,
+            c1: self.c1.clone() * k,
+            c2: self.c2.clone() * k,
+            xi: self.xi.clone(),
+        }
+    }
+
+    /// Return x^2
+    pub fn square(&self) -> Self {
+        self.clone() * self
+    }
+
+    /// Return the inverse of the number
+    ///
+    /// https://eprint.iacr.org/2010/354.pdf algorithm 17
+    pub fn inverse(&self) -> Self {
+        if self.is_zero() {
+            panic!("0 is not invertible");
+        }
+        let t0 = self.c0.square() - &(self.c1.clone() * &self.c2 * &self.xi);
+        let t1 = self.c2.square() * &self.xi - &(self.c0.clone() * &self.c1);
+        let t2 = self.c1.square() - &(self.c0.clone() * &self.c2);
+        let t = self.c0.clone() * &t0
+            + &((self.c2.clone() * &t1 + &(self.c1.clone() * &t2)) * &self.xi);
+        let t_inv = t.inverse();
+        Self {
+            c0: t0 * &t_inv,
+            c1: t1 * &t_inv,
+            c2: t2 * &t_inv,
+            xi: self.xi.clone(),
+        }
+    }
+
+    /// Return the number raised to the power of exp
+    pub fn pow(&self, exp: &BigUint) -> Self {
+        let mut result = self.to_one();
+        for i in (0..exp.bits()).rev() {
+            result = result.square();
+            if exp.bit(i) {
+                result = result * self;
+            }
+        }
+        result
+    }
+
+    fn check_field(&self, rhs: &Self) {
+        if self.xi != rhs.xi {
+            panic!("Cannot operate on two numbers in different fields");
+        }
+    }
+}
+
+impl Neg for Fp6 {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.to_zero() - &self
+    }
+}
+
+impl Add for Fp6 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self + &rhs
+    }
+}
+
+impl Sub for Fp6 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self - &rhs
+    }
+}
+
+impl Mul for Fp6 {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        self * &rhs
+    }
+}
+
+impl Div for Fp6 {
+    type Output = Self;
+
+    fn div(self, rhs: Self) -> Self::Output {
+        self / &rhs
+    }
+}
+
+impl Add<&Self> for Fp6 {
+    type Output = Self;
+
+    fn add(self, rhs: &Self) -> Self::Output {
+        self.check_field(rhs);
+        Self {
+            c0: self.c0 + &rhs.c0,
+            c1: self.c1 + &rhs.c1,
+            c2: self.c2 + &rhs.c2,
+            xi: self.xi,
+        }
+    }
+}
+
+impl Sub<&Self> for Fp6 {
+    type Output = Self;
+
+    fn sub(self, rhs: &Self) -> Self::Output {
+        self.check_field(rhs);
+        Self {
+            c0: self.c0 - &rhs.c0,
+            c1: self.c1 - &rhs.c1,
+            c2: self.c2 - &rhs.c2,
+            xi: self.xi,
+        }
+    }
+}
+
+impl Mul<&Self> for Fp6 {
+    type Output = Self;
+
+    /// schoolbook multiplication reduced with v^3 = xi
+    fn mul(self, rhs: &Self) -> Self::Output {
+        self.check_field(rhs);
+        let (a0, a1, a2) = (&self.c0, &self.c1, &self.c2);
+        let (b0, b1, b2) = (&rhs.c0, &rhs.c1, &rhs.c2);
+        let c0 = a0.clone() * b0 + &((a1.clone() * b2 + &(a2.clone() * b1)) * &self.xi);
+        let c1 = a0.clone() * b1 + &(a1.clone() * b0) + &(a2.clone() * b2 * &self.xi);
+        let c2 = a0.clone() * b2 + &(a1.clone() * b1) + &(a2.clone() * b0);
+        Self {
+            c0,
+            c1,
+            c2,
+            xi: self.xi,
+        }
+    }
+}
+
+impl Div<&Self> for Fp6 {
+    type Output = Self;
+
+    fn div(self, rhs: &Self) -> Self::Output {
+        self.mul(&rhs.inverse())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ff::FiniteField;
+    use crate::prime_field::PrimeField;
+    use num_traits::One;
+
+    fn fp2(c0: u32, c1: u32) -> Fp2 {
+        let prime = BigUint::from(103_u32);
+        Fp2::new(
+            PrimeField::new(BigUint::from(c0), prime.clone()),
+            PrimeField::new(BigUint::from(c1), prime),
+        )
+    }
+
+    #[test]
+    fn test_fp6_arithmetic() {
+        // 2 + u is neither a square nor a cube in F_103^2
+        let xi = fp2(2, 1);
+        let a = Fp6::new(fp2(3, 5), fp2(7, 2), fp2(11, 13), xi.clone());
+        let b = Fp6::new(fp2(17, 19), fp2(23, 29), fp2(31, 37), xi.clone());
+        let v = Fp6::new(xi.to_zero(), xi.to_one(), xi.to_zero(), xi.clone());
+        assert_eq!(v.pow(&BigUint::from(3_u32)), Fp6::from_fp2(xi.clone(), xi));
+        assert_eq!(a.mul_by_v(), a.clone() * &v);
+        assert_eq!(a.clone() * &b, b.clone() * &a);
+        assert_eq!(a.clone() * &a.inverse(), a.to_one());
+        assert_eq!((a.clone() / &b) * &b, a);
+        assert_eq!(-a.clone() + &a, a.to_zero());
+
+        // the multiplicative group has order 103^6 - 1
+        let order = BigUint::from(103_u32).pow(6) - BigUint::one();
+        assert_eq!(a.pow(&order), a.to_one());
+    }
+}
@@ -46,6 +46,56 @@ pub fn sqrt_root(x: BigUint, p: BigUint) -> BigUint {
     x.modpow(&p1, &p)
 }
 
+/// Tonelli-Shanks algorithm
+///
+/// return a square root of x modulo an odd prime p, or None if x is not a quadratic residue modulo p
+///
+/// unlike sqrt_root it works for every odd prime (example: 2^255 - 19 = 5 mod 8)
+pub fn tonelli_shanks(x: BigUint, p: BigUint) -> Option<BigUint> {
+    let x = x % &p;
+    if x.is_zero() {
+        return Some(x);
+    }
+    let one = BigUint::one();
+    let p_minus_1 = &p - &one;
+    if x.modpow(&(&p_minus_1 >> 1), &p) != one {
+        return None;
+    }
+
+    // p - 1 = q * 2^s with q odd
+    let s = p_minus_1.trailing_zeros().unwrap();
+    let q = &p_minus_1 >> s;
+    if s == 1 {
+        return Some(x.modpow(&((&p + &one) >> 2), &p));
+    }
+
+    // find a quadratic non-residue z
+    let mut z = BigUint::from(2_u32);
+    while z.modpow(&(&p_minus_1 >> 1), &p) != p_minus_1 {
+        z += &one;
+    }
+
+    let mut m = s;
+    let mut c = z.modpow(&q, &p);
+    let mut t = x.modpow(&q, &p);
+    let mut r = x.modpow(&((&q + &one) >> 1), &p);
+    while t != one {
+        // find the least i such that t^(2^i) = 1
+        let mut i = 0;
+        let mut t2i = t.clone();
+        while t2i != one {
+            t2i = &t2i * &t2i % &p;
+            i += 1;
+        }
+        let b = c.modpow(&(BigUint::one() << (m - i - 1)), &p);
+        m = i;
+        c = &b * &b % &p;
+        t = t * &c % &p;
+        r = r * &b % &p;
+    }
+    Some(r)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -77,4 +127,27 @@ mod tests {
         let result = sqrt_root(x, p);
         assert_eq!(result, BigUint::from(6_u32));
     }
+
+    #[test]
+    pub fn test_tonelli_shanks() {
+        // p = 13 = 1 mod 4
+        let p = BigUint::from(13_u32);
+        let root = tonelli_shanks(BigUint::from(10_u32), p.clone()).unwrap();
+        assert_eq!(&root * &root % &p, BigUint::from(10_u32));
+        assert_eq!(tonelli_shanks(BigUint::from(5_u32), p.clone()), None);
+        assert_eq!(tonelli_shanks(BigUint::zero(), p), Some(BigUint::zero()));
+
+        // p = 2^255 - 19 = 5 mod 8
+        let p = (BigUint::one() << 255_u32) - BigUint::from(19_u32);
+        let x = BigUint::from(123456789_u32).pow(2) % &p;
+        let root = tonelli_shanks(x.clone(), p.clone()).unwrap();
+        assert_eq!(&root * &root % &p, x);
+
+        // p = 23 = 3 mod 4 agrees with sqrt_root
+        let p = BigUint::from(23_u32);
+        assert_eq!(
+            tonelli_shanks(BigUint::from(13_u32), p.clone()),
+            Some(sqrt_root(BigUint::from(13_u32), p))
+        );
+    }
 }
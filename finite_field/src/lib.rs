@@ -1,7 +1,13 @@
 pub mod ff;
+pub mod fp12;
+pub mod fp2;
+pub mod fp6;
 pub mod helper;
 pub mod prime_field;
 
 pub use ff::*;
+pub use fp12::*;
+pub use fp2::*;
+pub use fp6::*;
 pub use helper::*;
 pub use prime_field::*;
@@ -122,7 +122,7 @@ impl fmt::Display for PrimeField {
     }
 }
 
-impl<'a> Add<&'a Self> for PrimeField {
+impl Add<&Self> for PrimeField {
     type Output = Self;
     fn add(self, other: &Self) -> Self::Output {
         if self.prime != other.prime {
@@ -136,7 +136,7 @@ impl<'a> Add<&'a Self> for PrimeField {
     }
 }
 
-impl<'a> Mul<&'a Self> for PrimeField {
+impl Mul<&Self> for PrimeField {
     type Output = Self;
     fn mul(self, other: &Self) -> Self::Output {
         if self.prime != other.prime {
@@ -150,7 +150,7 @@ impl<'a> Mul<&'a Self> for PrimeField {
     }
 }
 
-impl<'a> Sub<&'a Self> for PrimeField {
+impl Sub<&Self> for PrimeField {
     type Output = Self;
     fn sub(self, rhs: &Self) -> Self::Output {
         if self.prime != rhs.prime {
@@ -176,7 +176,7 @@ impl<'a> Sub<&'a Self> for PrimeField {
     }
 }
 
-impl<'a> Div<&'a Self> for PrimeField {
+impl Div<&Self> for PrimeField {
     type Output = Self;
     fn div(self, rhs: &Self) -> Self::Output {
         if self.prime != rhs.prime {